@@ -1,4 +1,6 @@
-use crate::utils::compress_utils;
+use crate::models::file_model::TreeOption;
+use crate::utils::compress_utils::{self, ArchiveError};
+use crate::utils::file_utils::{self, FileMetadata};
 use std::process::Command;
 
 /**
@@ -51,29 +53,68 @@ pub async fn open_folder(path: String) -> Result<(), String> {
     Ok(())
 }
 
+/**
+ * 获取文件的元数据，用于补全或刷新剪贴板记录中的文件信息
+ */
+#[tauri::command]
+pub async fn get_file_metadata(paths: Vec<String>) -> Result<Vec<FileMetadata>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        paths.iter().map(|path| file_utils::collect(path)).collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
 /**
  * 读取 RAR 压缩文件数据
  */
 #[tauri::command]
-pub fn read_rar_data(path: String) -> Result<String, ()> {
-    let data_list = compress_utils::parse_rar(&path);
-    Ok(serde_json::to_string(&data_list).unwrap())
+pub async fn read_rar_data(path: String) -> Result<Vec<TreeOption>, ArchiveError> {
+    read_archive(path, compress_utils::parse_rar).await
 }
 
 /**
  * 读取 TAR 压缩文件数据
  */
 #[tauri::command]
-pub fn read_tar_data(path: String) -> Result<String, ()> {
-    let data_list = compress_utils::parse_tar(&path);
-    Ok(serde_json::to_string(&data_list).unwrap())
+pub async fn read_tar_data(path: String) -> Result<Vec<TreeOption>, ArchiveError> {
+    read_archive(path, compress_utils::parse_tar).await
 }
 
 /**
  * 读取 GZIP 压缩文件数据
  */
 #[tauri::command]
-pub fn read_gzip_data(path: String) -> Result<String, ()> {
-    let data_list = compress_utils::parse_gzip(&path);
-    Ok(serde_json::to_string(&data_list).unwrap())
+pub async fn read_gzip_data(path: String) -> Result<Vec<TreeOption>, ArchiveError> {
+    read_archive(path, compress_utils::parse_gzip).await
+}
+
+/**
+ * 读取 ZIP 压缩文件数据
+ */
+#[tauri::command]
+pub async fn read_zip_data(path: String) -> Result<Vec<TreeOption>, ArchiveError> {
+    read_archive(path, compress_utils::parse_zip).await
+}
+
+/**
+ * 读取 7Z 压缩文件数据
+ */
+#[tauri::command]
+pub async fn read_7z_data(path: String) -> Result<Vec<TreeOption>, ArchiveError> {
+    read_archive(path, compress_utils::parse_7z).await
+}
+
+/**
+ * 在阻塞线程中读取压缩文件，解析库内部出错时也作为压缩文件损坏返回
+ */
+async fn read_archive(
+    path: String,
+    parse: fn(&str) -> Result<Vec<TreeOption>, ArchiveError>,
+) -> Result<Vec<TreeOption>, ArchiveError> {
+    tauri::async_runtime::spawn_blocking(move || parse(&path))
+        .await
+        .map_err(|e| ArchiveError::Corrupt {
+            message: e.to_string(),
+        })?
 }
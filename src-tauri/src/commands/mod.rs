@@ -1,5 +1,7 @@
 pub mod file;
+pub mod snippet;
 pub mod system;
+pub mod text;
 #[cfg(target_os = "windows")]
 pub mod regedit;
 #[cfg(debug_assertions)]
@@ -0,0 +1,37 @@
+use crate::listener::{self, ClipboardWriteRequest};
+use crate::utils::template_utils::{self, RenderedSnippet};
+use clipboard_rs::{Clipboard, ClipboardContext};
+use std::collections::HashMap;
+
+/**
+ * 获取片段中需要用户输入的名称
+ */
+#[tauri::command]
+pub fn get_snippet_prompts(content: String) -> Vec<String> {
+    template_utils::prompts(&content)
+}
+
+/**
+ * 渲染片段，可选将结果写入剪贴板
+ */
+#[tauri::command]
+pub fn render_snippet(
+    content: String,
+    values: Option<HashMap<String, String>>,
+    write_to_clipboard: Option<bool>,
+) -> Result<RenderedSnippet, String> {
+    let values = values.unwrap_or_default();
+    let rendered = template_utils::render(&content, &values, || {
+        ClipboardContext::new()
+            .and_then(|ctx| ctx.get_text())
+            .unwrap_or_default()
+    })?;
+    if write_to_clipboard.unwrap_or(false) {
+        let request = ClipboardWriteRequest::Text {
+            text: rendered.text.clone(),
+            normalize: None,
+        };
+        listener::write_to_clipboard(request).map_err(|e| e.to_string())?;
+    }
+    Ok(rendered)
+}
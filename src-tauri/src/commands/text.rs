@@ -0,0 +1,22 @@
+use crate::listener::{self, ClipboardWriteRequest};
+use crate::utils::text_utils::{self, TextTransform};
+
+/**
+ * 转换文本，可选将结果写入剪贴板
+ */
+#[tauri::command]
+pub fn transform_text(
+    text: String,
+    transform: TextTransform,
+    write_to_clipboard: Option<bool>,
+) -> Result<String, String> {
+    let result = text_utils::transform(&text, transform)?;
+    if write_to_clipboard.unwrap_or(false) {
+        let request = ClipboardWriteRequest::Text {
+            text: result.clone(),
+            normalize: None,
+        };
+        listener::write_to_clipboard(request).map_err(|e| e.to_string())?;
+    }
+    Ok(result)
+}
@@ -0,0 +1,51 @@
+use crate::files::state::{FileItemRecord, TrackedItem, FILE_STATE};
+use crate::files::validator;
+use log::warn;
+use tauri::AppHandle;
+
+/**
+ * 设置需要后台检查的所有文件记录，替换之前的记录并立即检查一次
+ * 路径没有变化的记录保留上次检查的结果
+ */
+#[tauri::command]
+pub fn set_tracked_file_items(app: AppHandle, items: Vec<FileItemRecord>) {
+    {
+        let mut state = FILE_STATE.lock().unwrap();
+        let mut previous = std::mem::take(&mut state.items);
+        for record in &items {
+            match TrackedItem::parse(record) {
+                Some(mut item) => {
+                    let old = previous.remove(&record.id);
+                    if let Some(old) = old.filter(|old| old.paths == item.paths) {
+                        item.missing = old.missing;
+                        item.failed_searches = old.failed_searches;
+                    }
+                    state.items.insert(record.id, item);
+                }
+                None => warn!("无法解析文件记录 {} 的路径", record.id),
+            }
+        }
+    }
+    validator::validate_soon(app);
+}
+
+/**
+ * 新增或更新一条需要后台检查的文件记录
+ */
+#[tauri::command]
+pub fn track_file_item(item: FileItemRecord) {
+    match TrackedItem::parse(&item) {
+        Some(tracked) => {
+            FILE_STATE.lock().unwrap().items.insert(item.id, tracked);
+        }
+        None => warn!("无法解析文件记录 {} 的路径", item.id),
+    }
+}
+
+/**
+ * 记录被删除后不再检查
+ */
+#[tauri::command]
+pub fn untrack_file_item(id: i64) {
+    FILE_STATE.lock().unwrap().items.remove(&id);
+}
@@ -0,0 +1,6 @@
+mod commands;
+mod state;
+mod validator;
+
+pub use commands::*;
+pub use validator::start_validator;
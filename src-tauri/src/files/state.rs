@@ -0,0 +1,118 @@
+use crate::utils::file_utils::FileMetadata;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/**
+ * 前端传入的文件类型剪贴板记录
+ */
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FileItemRecord {
+    pub id: i64,
+    /**
+     * 文件路径列表的 JSON
+     */
+    pub file_path: String,
+    /**
+     * 文件元数据列表的 JSON，旧记录没有
+     */
+    pub file_meta: Option<String>,
+}
+
+/**
+ * 后台检查的文件记录
+ */
+#[derive(Clone, Debug)]
+pub struct TrackedItem {
+    pub paths: Vec<String>,
+    /**
+     * 复制时采集的元数据，用于查找被移动的文件
+     */
+    pub meta: Vec<FileMetadata>,
+    /**
+     * 上次检查时不存在的路径
+     */
+    pub missing: Vec<String>,
+    /**
+     * 没有找到新位置的路径，以及查找时读取过的文件夹和修改时间，文件夹没有变化时不再查找
+     */
+    pub failed_searches: HashMap<String, SearchedDirs>,
+}
+
+/**
+ * 查找被移动的文件时读取过的文件夹及其修改时间
+ */
+pub type SearchedDirs = Vec<(PathBuf, Option<SystemTime>)>;
+
+impl TrackedItem {
+    /**
+     * 解析记录中的路径和元数据，路径无法解析时返回 None
+     */
+    pub fn parse(record: &FileItemRecord) -> Option<Self> {
+        let paths: Vec<String> = serde_json::from_str(&record.file_path).ok()?;
+        let meta = record
+            .file_meta
+            .as_deref()
+            .and_then(|meta| serde_json::from_str(meta).ok())
+            .unwrap_or_default();
+        Some(TrackedItem {
+            paths,
+            meta,
+            missing: Vec::new(),
+            failed_searches: HashMap::new(),
+        })
+    }
+
+    pub fn meta_of(&self, path: &str) -> Option<&FileMetadata> {
+        self.meta.iter().find(|meta| meta.path == path)
+    }
+}
+
+/**
+ * 被移动的文件
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Relocation {
+    pub from: String,
+    pub to: String,
+}
+
+/**
+ * 文件记录的检查结果，只在状态变化时通知前端
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FileItemValidation {
+    pub id: i64,
+    /**
+     * 更新后的文件路径列表
+     */
+    pub paths: Vec<String>,
+    /**
+     * 不存在并且没有找到新位置的路径
+     */
+    pub missing: Vec<String>,
+    pub relocated: Vec<Relocation>,
+    /**
+     * 更新后的元数据，有文件被移动时前端需要保存
+     */
+    pub meta: Vec<FileMetadata>,
+}
+
+#[derive(Default)]
+pub struct FileState {
+    pub items: HashMap<i64, TrackedItem>,
+}
+
+lazy_static! {
+    pub static ref FILE_STATE: Arc<Mutex<FileState>> = Arc::new(Mutex::new(FileState::default()));
+    /**
+     * 同一时间只进行一次检查
+     */
+    pub static ref VALIDATING: Mutex<()> = Mutex::new(());
+}
@@ -0,0 +1,220 @@
+use crate::files::state::{
+    FileItemValidation, Relocation, SearchedDirs, TrackedItem, FILE_STATE, VALIDATING,
+};
+use crate::utils::file_utils::{self, FileMetadata};
+use log::{info, warn};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter};
+
+/**
+ * 定时检查文件记录的间隔
+ */
+const VALIDATE_INTERVAL: Duration = Duration::from_secs(60);
+/**
+ * 查找被移动的文件时，向下搜索的子文件夹层数
+ */
+const SEARCH_DEPTH: usize = 2;
+/**
+ * 查找被移动的文件时最多检查的文件和文件夹数量，避免在大文件夹中耗时过长
+ */
+const SEARCH_LIMIT: usize = 5000;
+/**
+ * 原文件夹也不存在时，最多向上查找的层数
+ */
+const PARENT_LEVELS: usize = 2;
+
+/**
+ * 启动后台检查，定时检查文件记录是否被移动或删除
+ */
+pub fn start_validator(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(VALIDATE_INTERVAL).await;
+            let app = app.clone();
+            if let Err(e) = tauri::async_runtime::spawn_blocking(move || validate(&app)).await {
+                warn!("检查文件记录失败: {}", e);
+            }
+        }
+    });
+}
+
+/**
+ * 在后台立即检查一次
+ */
+pub fn validate_soon(app: AppHandle) {
+    tauri::async_runtime::spawn_blocking(move || validate(&app));
+}
+
+/**
+ * 检查所有文件记录，状态有变化时通知前端
+ */
+pub fn validate(app: &AppHandle) {
+    let _guard = VALIDATING.lock().unwrap();
+    let snapshot: Vec<(i64, TrackedItem)> = FILE_STATE
+        .lock()
+        .unwrap()
+        .items
+        .iter()
+        .map(|(id, item)| (*id, item.clone()))
+        .collect();
+
+    let mut changes = Vec::new();
+    for (id, item) in snapshot {
+        let (result, failed_searches) = check_item(&item);
+        let mut state = FILE_STATE.lock().unwrap();
+        // 检查期间记录被移除或重新设置时丢弃结果
+        let tracked = match state.items.get_mut(&id) {
+            Some(tracked) if tracked.paths == item.paths => tracked,
+            _ => continue,
+        };
+        tracked.failed_searches = failed_searches;
+        if result.relocated.is_empty() && result.missing == tracked.missing {
+            continue;
+        }
+        tracked.paths = result.paths.clone();
+        tracked.meta = result.meta.clone();
+        tracked.missing = result.missing.clone();
+        for relocation in &result.relocated {
+            info!("文件已被移动: {} -> {}", relocation.from, relocation.to);
+        }
+        changes.push(FileItemValidation { id, ..result });
+    }
+
+    if !changes.is_empty() {
+        let _ = app.emit("file-items-validated", changes);
+    }
+}
+
+/**
+ * 检查一条记录中的所有路径，不存在的文件尝试在原文件夹中查找
+ * 同时返回没有找到新位置的路径，上次查找过的文件夹都没有变化时不再查找
+ */
+fn check_item(item: &TrackedItem) -> (FileItemValidation, HashMap<String, SearchedDirs>) {
+    let mut result = FileItemValidation {
+        id: 0,
+        paths: Vec::with_capacity(item.paths.len()),
+        missing: Vec::new(),
+        relocated: Vec::new(),
+        meta: item.meta.clone(),
+    };
+    let mut failed_searches = HashMap::new();
+    for path in &item.paths {
+        if Path::new(path).exists() {
+            result.paths.push(path.clone());
+            continue;
+        }
+        if let Some(searched) = item.failed_searches.get(path) {
+            if unchanged(searched) {
+                failed_searches.insert(path.clone(), searched.clone());
+                result.missing.push(path.clone());
+                result.paths.push(path.clone());
+                continue;
+            }
+        }
+        let mut searched = Vec::new();
+        let found = item
+            .meta_of(path)
+            .and_then(|meta| relocate(meta, &item.paths, &mut searched));
+        match found {
+            Some(new_path) => {
+                let meta = file_utils::collect(&new_path);
+                match result.meta.iter_mut().find(|meta| &meta.path == path) {
+                    Some(old) => *old = meta,
+                    None => result.meta.push(meta),
+                }
+                result.relocated.push(Relocation {
+                    from: path.clone(),
+                    to: new_path.clone(),
+                });
+                result.paths.push(new_path);
+            }
+            None => {
+                failed_searches.insert(path.clone(), searched);
+                result.missing.push(path.clone());
+                result.paths.push(path.clone());
+            }
+        }
+    }
+    (result, failed_searches)
+}
+
+/**
+ * 判断查找过的文件夹是否都没有变化，没有读取过文件夹时也视为没有变化
+ */
+fn unchanged(searched: &SearchedDirs) -> bool {
+    searched
+        .iter()
+        .all(|(dir, modified)| modified_time(dir) == *modified)
+}
+
+fn modified_time(dir: &Path) -> Option<SystemTime> {
+    fs::metadata(dir)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/**
+ * 在原文件夹及其子文件夹中查找大小和快速哈希都相同的文件
+ * 原文件夹也不存在时从最近的上级文件夹开始查找，文件夹无法通过内容判断，不查找
+ * searched 记录读取过的文件夹及其修改时间
+ */
+fn relocate(meta: &FileMetadata, taken: &[String], searched: &mut SearchedDirs) -> Option<String> {
+    if meta.is_dir {
+        return None;
+    }
+    let hash = meta.hash.as_deref()?;
+    let root = Path::new(&meta.path)
+        .ancestors()
+        .skip(1)
+        .take(PARENT_LEVELS + 1)
+        .find(|dir| !dir.as_os_str().is_empty() && dir.is_dir())?;
+
+    let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::from([(root.to_path_buf(), 0)]);
+    let mut visited = 0;
+    while let Some((dir, depth)) = queue.pop_front() {
+        searched.push((dir.clone(), modified_time(&dir)));
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            visited += 1;
+            if visited > SEARCH_LIMIT {
+                return None;
+            }
+            // 不跟随符号链接，避免循环
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(_) => continue,
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                if depth < SEARCH_DEPTH {
+                    queue.push_back((path, depth + 1));
+                }
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let size = match entry.metadata() {
+                Ok(metadata) => metadata.len(),
+                Err(_) => continue,
+            };
+            if size != meta.size {
+                continue;
+            }
+            let candidate = path.to_string_lossy().into_owned();
+            if taken.contains(&candidate) {
+                continue;
+            }
+            if file_utils::quick_hash(&path, size).as_deref() == Some(hash) {
+                return Some(candidate);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,88 @@
+use crate::hotkeys::config::HotkeyAction;
+use crate::listener;
+use crate::paste;
+use crate::tray;
+use crate::tray::state::TRAY_STATE;
+use crate::windows;
+use log::{error, info};
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager, WebviewWindow};
+
+/**
+ * 执行快捷键对应的动作
+ */
+pub fn run(app: &AppHandle, action: &HotkeyAction) {
+    info!("快捷键触发: {:?}", action);
+    match action {
+        HotkeyAction::ToggleWindow => toggle_list_window(app),
+        HotkeyAction::ToggleListening => {
+            if listener::is_listening() {
+                listener::stop_listening(app.clone());
+            } else {
+                listener::start_listening(app.clone());
+            }
+        }
+        HotkeyAction::PasteRecent { index } => {
+            // 剪贴板记录由列表窗口加载，交给前端粘贴到当前焦点窗口
+            paste::remember_target_window();
+            let _ = app.emit_to("list", "quick-paste", json!({ "index": index }));
+        }
+        HotkeyAction::OpenSearch => {
+            if let Some(win) = list_window(app) {
+                if !win.is_visible().unwrap_or(false) {
+                    show_list_window(app, &win);
+                }
+                let _ = app.emit_to("list", "open-search", "");
+            }
+        }
+        HotkeyAction::PasteQueueNext => paste::paste_next(app),
+    }
+}
+
+fn list_window(app: &AppHandle) -> Option<WebviewWindow> {
+    let win = app.get_webview_window("list");
+    if win.is_none() {
+        error!("列表窗口不存在");
+    }
+    win
+}
+
+/**
+ * 显示或隐藏列表窗口
+ */
+fn toggle_list_window(app: &AppHandle) {
+    let win = match list_window(app) {
+        Some(win) => win,
+        None => return,
+    };
+    if win.is_visible().unwrap_or(false) {
+        if let Err(e) = win.hide() {
+            error!("隐藏列表窗口失败: {}", e);
+        }
+        tray::hide_win_msg();
+    } else {
+        show_list_window(app, &win);
+    }
+}
+
+/**
+ * 按配置的打开位置显示列表窗口
+ */
+fn show_list_window(app: &AppHandle, win: &WebviewWindow) {
+    // 记录当前焦点窗口，用于粘贴到目标窗口
+    paste::remember_target_window();
+    windows::place_list_window(app, win);
+
+    let result = win
+        .show()
+        .and_then(|_| win.unminimize())
+        .and_then(|_| win.set_focus());
+    if let Err(e) = result {
+        error!("显示列表窗口失败: {}", e);
+        return;
+    }
+    let mut state = TRAY_STATE.lock().unwrap();
+    state.win_current_visible = true;
+    state.win_enter_visible = true;
+    state.is_first_hide = false;
+}
@@ -0,0 +1,19 @@
+use crate::hotkeys::manager::{self, HotkeyStatus};
+use crate::hotkeys::state::HOTKEY_STATE;
+use tauri::AppHandle;
+
+/**
+ * 重新读取配置并注册快捷键
+ */
+#[tauri::command]
+pub fn reload_hotkeys(app: AppHandle) -> Vec<HotkeyStatus> {
+    manager::register_all(&app)
+}
+
+/**
+ * 获取最近一次快捷键注册的结果
+ */
+#[tauri::command]
+pub fn get_hotkey_statuses() -> Vec<HotkeyStatus> {
+    HOTKEY_STATE.lock().unwrap().statuses.clone()
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+/**
+ * 前端保存快捷键的配置文件
+ */
+const SHORTCUT_KEYS_FILE: &str = "shortcutKeys.json";
+/**
+ * 默认的粘贴队列快捷键
+ */
+const DEFAULT_QUEUE_SHORTCUT: &str = "CommandOrControl+Alt+V";
+/**
+ * 快速粘贴最近记录的数量，对应数字键 1-9
+ */
+const QUICK_PASTE_COUNT: usize = 9;
+
+/**
+ * 快捷键触发的动作
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum HotkeyAction {
+    /**
+     * 显示或隐藏列表窗口
+     */
+    ToggleWindow,
+    /**
+     * 暂停或恢复监听剪贴板
+     */
+    ToggleListening,
+    /**
+     * 粘贴第 index 条最近的记录，从 1 开始
+     */
+    PasteRecent { index: usize },
+    /**
+     * 打开列表窗口并显示搜索框
+     */
+    OpenSearch,
+    /**
+     * 粘贴队列中的下一项
+     */
+    PasteQueueNext,
+}
+
+impl HotkeyAction {
+    /**
+     * 会模拟粘贴按键的动作在松开快捷键时触发，避免和按住的快捷键组合，也避免按住时重复触发
+     */
+    pub fn fires_on_release(&self) -> bool {
+        matches!(
+            self,
+            HotkeyAction::PasteRecent { .. } | HotkeyAction::PasteQueueNext
+        )
+    }
+}
+
+/**
+ * 快捷键绑定
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyBinding {
+    /**
+     * 快捷键，格式同 tauri 全局快捷键，如 Alt+C
+     */
+    pub shortcut: String,
+    #[serde(flatten)]
+    pub action: HotkeyAction,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/**
+ * 读取快捷键绑定，settings.json 中没有 hotkeys 配置时使用快捷键设置页面保存的配置
+ */
+pub fn load_bindings(app: &AppHandle) -> Vec<HotkeyBinding> {
+    let configured = app
+        .store("settings.json")
+        .ok()
+        .and_then(|store| store.get("hotkeys"))
+        .and_then(|v| serde_json::from_value::<Vec<HotkeyBinding>>(v).ok());
+    match configured {
+        Some(bindings) => bindings,
+        None => default_bindings(app),
+    }
+}
+
+/**
+ * 由唤醒程序、快速粘贴和粘贴队列快捷键生成绑定
+ */
+fn default_bindings(app: &AppHandle) -> Vec<HotkeyBinding> {
+    let mut bindings = Vec::new();
+    let shortcut_keys = app.store(SHORTCUT_KEYS_FILE).ok();
+    let keys = |name: &str| -> Option<Vec<String>> {
+        let value: Value = shortcut_keys.as_ref()?.get(name)?;
+        serde_json::from_value::<Vec<String>>(value.get("key")?.clone())
+            .ok()
+            .filter(|keys| !keys.is_empty())
+    };
+
+    let wake_up = keys("wakeUpRoutine").unwrap_or_else(|| vec!["alt".into(), "c".into()]);
+    bindings.push(HotkeyBinding {
+        shortcut: to_shortcut(&wake_up),
+        action: HotkeyAction::ToggleWindow,
+        enabled: true,
+    });
+
+    let quick_paste = keys("quickPaste").unwrap_or_else(|| vec!["ctrl".into(), "alt".into()]);
+    for index in 1..=QUICK_PASTE_COUNT {
+        let mut keys = quick_paste.clone();
+        keys.push(index.to_string());
+        bindings.push(HotkeyBinding {
+            shortcut: to_shortcut(&keys),
+            action: HotkeyAction::PasteRecent { index },
+            enabled: true,
+        });
+    }
+
+    let queue_shortcut = app
+        .store("settings.json")
+        .ok()
+        .and_then(|store| store.get("pasteQueueShortcut"))
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| DEFAULT_QUEUE_SHORTCUT.to_string());
+    bindings.push(HotkeyBinding {
+        shortcut: queue_shortcut,
+        action: HotkeyAction::PasteQueueNext,
+        enabled: true,
+    });
+    bindings
+}
+
+/**
+ * 把前端保存的按键列表转换为快捷键，Win/Command 键在前端记录为 meta
+ */
+fn to_shortcut(keys: &[String]) -> String {
+    keys.iter()
+        .map(|key| match key.to_lowercase().as_str() {
+            "meta" => "Super".to_string(),
+            _ => key.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
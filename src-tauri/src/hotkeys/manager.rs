@@ -0,0 +1,132 @@
+use crate::hotkeys::actions;
+use crate::hotkeys::config::{self, HotkeyAction};
+use crate::hotkeys::state::HOTKEY_STATE;
+use log::{error, info, warn};
+use serde::Serialize;
+use std::str::FromStr;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+/**
+ * 快捷键注册结果
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "state",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum HotkeyResult {
+    Registered,
+    /**
+     * 未启用
+     */
+    Disabled,
+    /**
+     * 快捷键格式无效
+     */
+    Invalid { message: String },
+    /**
+     * 和前面的绑定使用了相同的快捷键
+     */
+    Conflict { with: HotkeyAction },
+    /**
+     * 注册失败，通常是快捷键已被其他程序占用
+     */
+    Failed { message: String },
+}
+
+/**
+ * 单个快捷键绑定的注册状态
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyStatus {
+    pub shortcut: String,
+    #[serde(flatten)]
+    pub action: HotkeyAction,
+    pub result: HotkeyResult,
+}
+
+/**
+ * 注销已注册的快捷键后按配置重新注册，返回每个绑定的注册结果
+ */
+pub fn register_all(app: &AppHandle) -> Vec<HotkeyStatus> {
+    let global_shortcut = app.global_shortcut();
+    let mut state = HOTKEY_STATE.lock().unwrap();
+    for shortcut in state.registered.drain(..) {
+        if let Err(e) = global_shortcut.unregister(shortcut) {
+            warn!("注销快捷键失败: {}", e);
+        }
+    }
+
+    let mut parsed: Vec<(Shortcut, HotkeyAction)> = Vec::new();
+    let mut statuses = Vec::new();
+    for binding in config::load_bindings(app) {
+        let result = if !binding.enabled {
+            HotkeyResult::Disabled
+        } else {
+            match Shortcut::from_str(&binding.shortcut) {
+                Err(e) => HotkeyResult::Invalid {
+                    message: e.to_string(),
+                },
+                Ok(shortcut) => {
+                    let conflict = parsed
+                        .iter()
+                        .find(|(s, _)| s.id() == shortcut.id())
+                        .map(|(_, action)| action.clone());
+                    match conflict {
+                        Some(with) => HotkeyResult::Conflict { with },
+                        None => {
+                            parsed.push((shortcut, binding.action.clone()));
+                            let result = register(app, shortcut, binding.action.clone());
+                            if let HotkeyResult::Registered = result {
+                                state.registered.push(shortcut);
+                            }
+                            result
+                        }
+                    }
+                }
+            }
+        };
+        if !matches!(result, HotkeyResult::Registered | HotkeyResult::Disabled) {
+            error!("快捷键注册失败: {}, {:?}", binding.shortcut, result);
+        }
+        statuses.push(HotkeyStatus {
+            shortcut: binding.shortcut,
+            action: binding.action,
+            result,
+        });
+    }
+
+    info!(
+        "快捷键注册完成，成功 {} 个，共 {} 个",
+        state.registered.len(),
+        statuses.len()
+    );
+    state.statuses = statuses.clone();
+    drop(state);
+    let _ = app.emit("hotkeys-registered", statuses.clone());
+    statuses
+}
+
+fn register(app: &AppHandle, shortcut: Shortcut, action: HotkeyAction) -> HotkeyResult {
+    let trigger = if action.fires_on_release() {
+        ShortcutState::Released
+    } else {
+        ShortcutState::Pressed
+    };
+    let result = app
+        .global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state == trigger {
+                actions::run(app, &action);
+            }
+        });
+    match result {
+        Ok(_) => HotkeyResult::Registered,
+        Err(e) => HotkeyResult::Failed {
+            message: e.to_string(),
+        },
+    }
+}
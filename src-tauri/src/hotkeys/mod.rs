@@ -0,0 +1,8 @@
+mod actions;
+mod commands;
+mod config;
+mod manager;
+mod state;
+
+pub use commands::*;
+pub use manager::register_all;
@@ -0,0 +1,21 @@
+use crate::hotkeys::manager::HotkeyStatus;
+use lazy_static::lazy_static;
+use std::sync::{Arc, Mutex};
+use tauri_plugin_global_shortcut::Shortcut;
+
+#[derive(Default)]
+pub struct HotkeyState {
+    /**
+     * 已注册的快捷键，重新注册前需要先注销
+     */
+    pub registered: Vec<Shortcut>,
+    /**
+     * 最近一次注册的结果
+     */
+    pub statuses: Vec<HotkeyStatus>,
+}
+
+lazy_static! {
+    pub static ref HOTKEY_STATE: Arc<Mutex<HotkeyState>> =
+        Arc::new(Mutex::new(HotkeyState::default()));
+}
@@ -1,6 +1,6 @@
 use std::fs;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use log::warn;
 use serde_json::from_reader;
 use tauri::{AppHandle, Manager};
@@ -8,21 +8,25 @@ use tauri::path::BaseDirectory;
 use tauri_plugin_store::StoreExt;
 use crate::i18n::I18nState;
 use crate::i18n::models::PluginLanguage;
+use crate::plugins::{load_plugin_manifest, ManifestError, PluginManifest};
 
 /**
  * 加载所有插件的语言
  */
 pub fn load_plugins_locales(app: AppHandle, state: &I18nState) {
     println!("加载插件语言");
-    let plugins_path = get_plugins_path(app);
+    let plugins_path = get_plugins_path(app.clone());
     println!("插件文件所在目录: {:?}", plugins_path);
     if plugins_path.exists() {
         // 遍历插件目录
         for plugin_entry in fs::read_dir(plugins_path).expect("无法读取插件目录") {
             let plugin_entry = plugin_entry.expect("无效的插件目录");
             let plugin_path = plugin_entry.path();
-            println!("正在加载的插件目录: {:?}", plugin_path);
-            load_plugin_locales(state, plugin_path);
+            if plugin_path.is_dir() {
+                println!("正在加载的插件目录: {:?}", plugin_path);
+                let plugin_id = plugin_entry.file_name().to_string_lossy().to_string();
+                load_plugin_locales(&app, state, &plugin_id);
+            }
         }
     }
     println!("插件语言加载完成");
@@ -33,68 +37,112 @@ pub fn load_plugins_locales(app: AppHandle, state: &I18nState) {
  */
 pub fn load_plugin_locales_by_plugin_id(app: AppHandle, state: &I18nState, plugin_id: &str) {
     println!("加载 {} 插件语言", plugin_id);
-    let plugins_path = get_plugins_path(app);
-    println!("插件文件所在目录: {:?}", plugins_path);
-    if plugins_path.exists() {
-        let plugin_path = plugins_path.join(plugin_id);
-        load_plugin_locales(state, plugin_path);
-    }
+    load_plugin_locales(&app, state, plugin_id);
     println!("{} 插件语言加载完成", plugin_id);
 }
 
 /**
- * 加载插件语言
+ * 加载插件语言，只加载清单校验通过的插件中声明的语言文件
  */
-fn load_plugin_locales(state: &I18nState, plugin_path: PathBuf) {
-    if plugin_path.is_dir() {
-        let locales_path = plugin_path.join("locales");
-        if locales_path.exists() {
-            println!("插件语言文件位置: {:?}", locales_path);
-            for locale_entry in fs::read_dir(locales_path).expect("无法读取插件语言目录")
-            {
-                let locale_entry = locale_entry.expect("无效的插件语言目录");
-                let locale_path = locale_entry.path();
-                if locale_path.is_file()
-                    && locale_path
-                    .file_name()
-                    .expect("文件名获取失败")
-                    .to_string_lossy()
-                    .ends_with(".json")
-                {
-                    println!("正在加载插件语言文件: {:?}", locale_path);
-                    match File::open(locale_path) {
-                        Ok(file) => match from_reader::<File, PluginLanguage>(file) {
-                            Ok(data) => {
-                                state.insert_plugin_locale(data);
-                            }
-                            Err(e) => {
-                                warn!(
-                                        "解析插件语言文件失败: {:?}, 错误: {}",
-                                        locale_entry.file_name(),
-                                        e
-                                    );
-                            }
-                        },
-                        Err(_) => {
-                            warn!(
-                                    "无法打开插件语言文件: {:?}, {:?}",
-                                    plugin_path.file_name(),
-                                    locale_entry.file_name()
-                                );
-                        }
+fn load_plugin_locales(app: &AppHandle, state: &I18nState, plugin_id: &str) {
+    let (plugin_path, manifest) = match load_plugin_manifest(app, plugin_id) {
+        Ok(result) => result,
+        Err(e @ ManifestError::ManifestMissing { .. }) => {
+            warn!("{}，按旧版本目录加载插件语言", e);
+            let plugin_path = get_plugins_path(app.clone()).join(plugin_id);
+            load_legacy_plugin_locales(state, &plugin_path, plugin_id);
+            return;
+        }
+        Err(e) => {
+            warn!("插件 {} 校验失败，不加载插件语言: {}", plugin_id, e);
+            return;
+        }
+    };
+
+    for locale_path in manifest.locale_paths(&plugin_path) {
+        println!("正在加载插件语言文件: {:?}", locale_path);
+        match File::open(&locale_path) {
+            Ok(file) => match from_reader::<File, PluginLanguage>(file) {
+                Ok(data) => {
+                    if data.plugin_id != manifest.id {
+                        warn!(
+                            "插件语言文件的插件id不匹配: {:?}, 期望: {}, 实际: {}",
+                            locale_path, manifest.id, data.plugin_id
+                        );
+                        continue;
                     }
+                    state.insert_plugin_locale(data);
+                }
+                Err(e) => {
+                    warn!("解析插件语言文件失败: {:?}, 错误: {}", locale_path, e);
                 }
+            },
+            Err(_) => {
+                warn!("无法打开插件语言文件: {}, {:?}", plugin_id, locale_path);
             }
-        } else {
-            println!("插件语言目录不存在: {:?}", plugin_path.file_name());
         }
     }
 }
 
+/**
+ * 加载旧版本插件 locales 目录下的语言文件，插件列表在用户重新安装前仍能显示插件的翻译
+ */
+fn load_legacy_plugin_locales(state: &I18nState, plugin_path: &Path, plugin_id: &str) {
+    let locales_path = plugin_path.join("locales");
+    let entries = match fs::read_dir(&locales_path) {
+        Ok(entries) => entries,
+        Err(_) => {
+            println!("插件语言目录不存在: {:?}", locales_path);
+            return;
+        }
+    };
+    for locale_entry in entries.flatten() {
+        let locale_path = locale_entry.path();
+        if !locale_path.is_file() || locale_path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        println!("正在加载插件语言文件: {:?}", locale_path);
+        let data = File::open(&locale_path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| from_reader::<File, PluginLanguage>(file).map_err(|e| e.to_string()));
+        match data {
+            Ok(data) if data.plugin_id == plugin_id => state.insert_plugin_locale(data),
+            Ok(data) => warn!(
+                "插件语言文件的插件id不匹配: {:?}, 期望: {}, 实际: {}",
+                locale_path, plugin_id, data.plugin_id
+            ),
+            Err(e) => warn!("解析插件语言文件失败: {:?}, 错误: {}", locale_path, e),
+        }
+    }
+}
+
+/**
+ * 读取插件声明的全部语言文件，任何一个文件不合法都返回错误，用于安装前校验
+ */
+pub(crate) fn read_plugin_locales(
+    plugin_path: &Path,
+    manifest: &PluginManifest,
+) -> Result<Vec<PluginLanguage>, (PathBuf, String)> {
+    let mut locales = Vec::new();
+    for locale_path in manifest.locale_paths(plugin_path) {
+        let file = File::open(&locale_path).map_err(|e| (locale_path.clone(), e.to_string()))?;
+        let data = from_reader::<File, PluginLanguage>(file)
+            .map_err(|e| (locale_path.clone(), e.to_string()))?;
+        if data.plugin_id != manifest.id {
+            return Err((
+                locale_path,
+                format!("插件id不匹配, 期望: {}, 实际: {}", manifest.id, data.plugin_id),
+            ));
+        }
+        locales.push(data);
+    }
+    Ok(locales)
+}
+
 /**
  * 获取插件目录
  */
-fn get_plugins_path(app: AppHandle) -> PathBuf {
+pub(crate) fn get_plugins_path(app: AppHandle) -> PathBuf {
     let plugins_path: PathBuf = match app.store("settings.json") {
         Ok(store) => {
             if store.has("pluginPath") {
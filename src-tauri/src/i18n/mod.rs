@@ -9,3 +9,4 @@ mod loader_plugins;
 pub use state::I18nState;
 pub use loader::init_locale;
 pub use commands::*;
+pub(crate) use loader_plugins::{get_plugins_path, read_plugin_locales};
@@ -69,6 +69,57 @@ pub struct Tray {
     pub exit: String,
     #[serde(rename = "clipboardMonitor")]
     pub clipboard_monitor: String,
+    #[serde(rename = "pasteQueue", default = "default_paste_queue")]
+    pub paste_queue: String,
+    #[serde(rename = "clearPasteQueue", default = "default_clear_paste_queue")]
+    pub clear_paste_queue: String,
+    #[serde(rename = "pauseListening", default = "default_pause_listening")]
+    pub pause_listening: String,
+    #[serde(rename = "pauseFiveMinutes", default = "default_pause_five_minutes")]
+    pub pause_five_minutes: String,
+    #[serde(rename = "pauseOneHour", default = "default_pause_one_hour")]
+    pub pause_one_hour: String,
+    #[serde(rename = "pauseUntilRestart", default = "default_pause_until_restart")]
+    pub pause_until_restart: String,
+    /**
+     * 暂停倒计时，{minutes} 替换为剩余分钟数
+     */
+    #[serde(rename = "pausedRemaining", default = "default_paused_remaining")]
+    pub paused_remaining: String,
+    #[serde(rename = "pausedUntilRestart", default = "default_paused_until_restart")]
+    pub paused_until_restart: String,
+}
+
+fn default_paste_queue() -> String {
+    "粘贴队列".to_string()
+}
+
+fn default_clear_paste_queue() -> String {
+    "清空粘贴队列".to_string()
+}
+
+fn default_pause_listening() -> String {
+    "暂停监听".to_string()
+}
+
+fn default_pause_five_minutes() -> String {
+    "5 分钟".to_string()
+}
+
+fn default_pause_one_hour() -> String {
+    "1 小时".to_string()
+}
+
+fn default_pause_until_restart() -> String {
+    "直到重启".to_string()
+}
+
+fn default_paused_remaining() -> String {
+    "已暂停，{minutes} 分钟后恢复".to_string()
+}
+
+fn default_paused_until_restart() -> String {
+    "已暂停，重启后恢复".to_string()
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -55,6 +55,31 @@ impl I18nState {
         }
     }
 
+    /**
+     * 一次性注册插件的全部语言，避免界面读到只注册了一部分的语言
+     */
+    pub fn insert_plugin_locales(&self, plugin_locales: Vec<PluginLanguage>) {
+        let mut locales = self.locales.lock().unwrap();
+        insert_plugin_locales(&mut locales, plugin_locales);
+    }
+
+    /**
+     * 从所有语言中移除插件的翻译
+     */
+    pub fn remove_plugin_locales(&self, plugin_id: &str) {
+        let mut locales = self.locales.lock().unwrap();
+        remove_plugin_locales(&mut locales, plugin_id);
+    }
+
+    /**
+     * 用新版本插件的语言替换旧版本的翻译
+     */
+    pub fn replace_plugin_locales(&self, plugin_id: &str, plugin_locales: Vec<PluginLanguage>) {
+        let mut locales = self.locales.lock().unwrap();
+        remove_plugin_locales(&mut locales, plugin_id);
+        insert_plugin_locales(&mut locales, plugin_locales);
+    }
+
     pub fn get_tray(&self) -> Tray {
         let option = self.get_locale_by_id(self.get_current_locale_id().as_str());
         if let Some(tray) = option.map(|l| l.pages.tray) {
@@ -67,9 +92,43 @@ impl I18nState {
                 "checkUpdate": "检查更新",
                 "about": "关于",
                 "restart": "重启",
-                "exit": "退出"
+                "exit": "退出",
+                "pasteQueue": "粘贴队列",
+                "clearPasteQueue": "清空粘贴队列",
+                "pauseListening": "暂停监听",
+                "pauseFiveMinutes": "5 分钟",
+                "pauseOneHour": "1 小时",
+                "pauseUntilRestart": "直到重启",
+                "pausedRemaining": "已暂停，{minutes} 分钟后恢复",
+                "pausedUntilRestart": "已暂停，重启后恢复"
             }"#;
             from_slice(default_json.as_bytes()).unwrap()
         }
     }
 }
+
+fn insert_plugin_locales(
+    locales: &mut HashMap<String, LanguageConfig>,
+    plugin_locales: Vec<PluginLanguage>,
+) {
+    for plugin_locale in plugin_locales {
+        for (_, config) in locales.iter_mut() {
+            if config.id.eq(&plugin_locale.id) {
+                if let Some(plugins_value) = config.pages.plugins.as_object_mut() {
+                    plugins_value.insert(
+                        plugin_locale.plugin_id.clone(),
+                        plugin_locale.locale.clone(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn remove_plugin_locales(locales: &mut HashMap<String, LanguageConfig>, plugin_id: &str) {
+    for (_, config) in locales.iter_mut() {
+        if let Some(plugins_value) = config.pages.plugins.as_object_mut() {
+            plugins_value.remove(plugin_id);
+        }
+    }
+}
@@ -2,11 +2,15 @@
 use tauri::Manager;
 
 mod commands;
+mod files;
+mod hotkeys;
 mod models;
 mod utils;
 
 mod listener;
 mod log;
+mod paste;
+mod plugins;
 mod tray;
 mod windows;
 mod i18n;
@@ -15,6 +19,7 @@ mod i18n;
 pub fn run() {
     tauri::Builder::default()
         .manage(i18n::I18nState::new())
+        .manage(plugins::PluginHost::new())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_os::init())
@@ -24,18 +29,25 @@ pub fn run() {
                 tauri_plugin_autostart::MacosLauncher::LaunchAgent,
                 Some(vec!["--flag1", "--flag2"]), /* arbitrary number of args to pass to your app */
             ));
-            // 开始监听
-            listener::start_listening(app.handle().clone());
             // 初始化系统语言
             i18n::init_locale(app.handle().clone());
             // 创建系统托盘
             tray::create_tray(app.handle().clone());
+            // 开始监听
+            listener::start_listening(app.handle().clone());
+            // 后台检查文件记录是否被移动或删除
+            files::start_validator(app.handle().clone());
+            // 注册全局快捷键
+            hotkeys::register_all(app.handle());
             // 创建主窗口
             windows::create_main_window(app.handle().clone());
             Ok(())
         })
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             let win = app.get_webview_window("list").expect("主窗口不存在");
+            if !win.is_visible().unwrap_or(false) {
+                windows::place_list_window(app, &win);
+            }
             win.show().expect("窗口显示失败");
             win.set_focus().expect("窗口聚焦失败");
         }))
@@ -56,6 +68,19 @@ pub fn run() {
             tray::hide_win_msg,
             listener::write_to_clipboard,
             listener::is_listening,
+            listener::pause_clipboard_listening,
+            listener::get_listening_status,
+            listener::get_listener_health,
+            listener::restart_clipboard_listening,
+            paste::remember_target_window,
+            paste::paste_to_target,
+            paste::enqueue_paste,
+            paste::get_paste_queue,
+            paste::reorder_paste_queue,
+            paste::remove_from_paste_queue,
+            paste::clear_paste_queue,
+            hotkeys::reload_hotkeys,
+            hotkeys::get_hotkey_statuses,
             #[cfg(target_os = "windows")]
             commands::regedit::valid_clipboard_regedit,
             #[cfg(target_os = "windows")]
@@ -66,10 +91,29 @@ pub fn run() {
             commands::regedit::recover_clipboard_regedit,
             commands::system::check_admin,
             commands::file::open_folder,
+            commands::file::get_file_metadata,
+            files::set_tracked_file_items,
+            files::track_file_item,
+            files::untrack_file_item,
             commands::file::read_rar_data,
             commands::file::read_tar_data,
             commands::file::read_gzip_data,
-            windows::invoke_external_plugin,
+            commands::file::read_zip_data,
+            commands::file::read_7z_data,
+            commands::text::transform_text,
+            commands::snippet::get_snippet_prompts,
+            commands::snippet::render_snippet,
+            plugins::invoke_external_plugin,
+            plugins::cancel_external_plugin,
+            plugins::stop_external_plugin,
+            plugins::get_plugin_manifest,
+            plugins::get_plugin_permissions,
+            plugins::approve_plugin_permissions,
+            plugins::resolve_plugin_history,
+            plugins::inspect_plugin_archive,
+            plugins::install_plugin,
+            plugins::upgrade_plugin,
+            plugins::uninstall_plugin,
             windows::init_main_window,
             commands::web::fetch_page_title,
             i18n::get_current_locale,
@@ -79,6 +123,12 @@ pub fn run() {
             i18n::get_ui_locale,
             i18n::load_plugin_locales,
         ])
-        .run(tauri::generate_context!())
-        .expect("应用程序运行异常");
+        .build(tauri::generate_context!())
+        .expect("应用程序运行异常")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // 退出时停止所有插件进程
+                app.state::<plugins::PluginHost>().stop_all();
+            }
+        });
 }
\ No newline at end of file
@@ -1,28 +1,240 @@
-use clipboard_rs::{Clipboard, ClipboardContext};
+use crate::listener::state::LISTENER_STATE;
+use crate::utils::text_utils::{self, NormalizeOptions};
+use clipboard_rs::common::RustImage;
+use clipboard_rs::{Clipboard, ClipboardContent, ClipboardContext, RustImageData};
 use log::error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::time::Instant;
+
+/**
+ * 写入剪贴板的请求
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(
+    tag = "format",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ClipboardWriteRequest {
+    /**
+     * 文本，可按选项规范化后写入
+     */
+    Text {
+        text: String,
+        normalize: Option<NormalizeOptions>,
+    },
+    /**
+     * 只写入纯文本，html 为内容的 HTML 格式，提供时从中提取纯文本，否则写入 text
+     */
+    PlainText {
+        text: String,
+        html: Option<String>,
+        normalize: Option<NormalizeOptions>,
+    },
+    /**
+     * HTML，alt_text 为不支持 HTML 的应用提供纯文本
+     */
+    Html {
+        html: String,
+        alt_text: Option<String>,
+    },
+    /**
+     * RTF 富文本，alt_text 为不支持 RTF 的应用提供纯文本
+     */
+    Rtf {
+        rtf: String,
+        alt_text: Option<String>,
+    },
+    /**
+     * 图片以图片数据写入，include_file 为 true 时同时写入图片文件
+     */
+    Image {
+        path: String,
+        #[serde(default)]
+        include_file: bool,
+    },
+    /**
+     * 文件列表
+     */
+    Files { paths: Vec<String> },
+    /**
+     * 同时写入多种格式，由粘贴的应用选择需要的格式
+     */
+    Multi { items: Vec<ClipboardWriteRequest> },
+}
+
+/**
+ * 写入剪贴板失败的原因
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ClipboardWriteError {
+    /**
+     * 无法打开系统剪贴板
+     */
+    Unavailable { message: String },
+    /**
+     * 文件不存在
+     */
+    FileNotFound { path: String },
+    /**
+     * 图片文件无法读取
+     */
+    ImageUnreadable { path: String, message: String },
+    /**
+     * 没有需要写入的内容
+     */
+    EmptyContent,
+    /**
+     * 多格式写入中不能再嵌套多格式
+     */
+    NestedMulti,
+    /**
+     * 系统剪贴板写入失败
+     */
+    WriteFailed { message: String },
+}
+
+impl fmt::Display for ClipboardWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardWriteError::Unavailable { message } => {
+                write!(f, "无法打开系统剪贴板: {}", message)
+            }
+            ClipboardWriteError::FileNotFound { path } => write!(f, "文件不存在: {}", path),
+            ClipboardWriteError::ImageUnreadable { path, message } => {
+                write!(f, "图片读取失败: {}, {}", path, message)
+            }
+            ClipboardWriteError::EmptyContent => write!(f, "没有需要写入剪贴板的内容"),
+            ClipboardWriteError::NestedMulti => write!(f, "多格式写入中不能嵌套多格式"),
+            ClipboardWriteError::WriteFailed { message } => {
+                write!(f, "写入剪贴板失败: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClipboardWriteError {}
 
 /**
  * 写入剪贴板
  */
 #[tauri::command]
-pub fn write_to_clipboard(content: String, format: String) -> bool {
-    let ctx = ClipboardContext::new().unwrap();
+pub fn write_to_clipboard(request: ClipboardWriteRequest) -> Result<(), ClipboardWriteError> {
+    let ctx = ClipboardContext::new().map_err(|e| ClipboardWriteError::Unavailable {
+        message: e.to_string(),
+    })?;
 
-    match format.as_str() {
-        "text" => ctx.set_text(content).is_ok(),
-        "image" => {
-            let mut paths: Vec<String> = Vec::new();
-            paths.push(content);
-            ctx.set_files(paths).is_ok()
+    let mut contents = Vec::new();
+    match request {
+        ClipboardWriteRequest::Multi { items } => {
+            for item in items {
+                if let ClipboardWriteRequest::Multi { .. } = item {
+                    return Err(ClipboardWriteError::NestedMulti);
+                }
+                push_contents(item, &mut contents)?;
+            }
         }
-        "files" => {
-            let paths = serde_json::from_str::<Vec<String>>(&content).unwrap();
-            ctx.set_files(paths).is_ok()
+        request => push_contents(request, &mut contents)?,
+    }
+    if contents.is_empty() {
+        return Err(ClipboardWriteError::EmptyContent);
+    }
+
+    // 记录写入图片的时间，避免监听到自己写入的图片后重复保存
+    if contents
+        .iter()
+        .any(|c| matches!(c, ClipboardContent::Image(_)))
+    {
+        LISTENER_STATE.lock().unwrap().image_written_at = Some(Instant::now());
+    }
+
+    ctx.set(contents).map_err(|e| {
+        error!("写入剪贴板失败:{}", e);
+        ClipboardWriteError::WriteFailed {
+            message: e.to_string(),
+        }
+    })
+}
+
+/**
+ * 把写入请求转换为剪贴板内容
+ */
+fn push_contents(
+    request: ClipboardWriteRequest,
+    contents: &mut Vec<ClipboardContent>,
+) -> Result<(), ClipboardWriteError> {
+    match request {
+        ClipboardWriteRequest::Text { text, normalize } => {
+            contents.push(ClipboardContent::Text(normalize_text(&text, &normalize)));
+        }
+        ClipboardWriteRequest::PlainText {
+            text,
+            html,
+            normalize,
+        } => {
+            let text = html.map_or(text, |html| text_utils::to_plain_text(&html));
+            contents.push(ClipboardContent::Text(normalize_text(&text, &normalize)));
+        }
+        ClipboardWriteRequest::Html { html, alt_text } => {
+            contents.push(ClipboardContent::Html(html));
+            if let Some(text) = alt_text {
+                contents.push(ClipboardContent::Text(text));
+            }
         }
-        _ => {
-            error!("文件类型不支持:{}复制到剪贴板", format);
-            false
+        ClipboardWriteRequest::Rtf { rtf, alt_text } => {
+            contents.push(ClipboardContent::Rtf(rtf));
+            if let Some(text) = alt_text {
+                contents.push(ClipboardContent::Text(text));
+            }
         }
+        ClipboardWriteRequest::Image { path, include_file } => {
+            ensure_exists(&path)?;
+            let image = RustImageData::from_path(&path).map_err(|e| {
+                ClipboardWriteError::ImageUnreadable {
+                    path: path.clone(),
+                    message: e.to_string(),
+                }
+            })?;
+            contents.push(ClipboardContent::Image(image));
+            if include_file {
+                contents.push(ClipboardContent::Files(vec![path]));
+            }
+        }
+        ClipboardWriteRequest::Files { paths } => {
+            if paths.is_empty() {
+                return Err(ClipboardWriteError::EmptyContent);
+            }
+            for path in &paths {
+                ensure_exists(path)?;
+            }
+            contents.push(ClipboardContent::Files(paths));
+        }
+        ClipboardWriteRequest::Multi { .. } => return Err(ClipboardWriteError::NestedMulti),
+    }
+    Ok(())
+}
+
+fn normalize_text(text: &str, normalize: &Option<NormalizeOptions>) -> String {
+    match normalize {
+        Some(options) => text_utils::normalize(text, options),
+        None => text.to_string(),
+    }
+}
+
+fn ensure_exists(path: &str) -> Result<(), ClipboardWriteError> {
+    if Path::new(path).exists() {
+        Ok(())
+    } else {
+        Err(ClipboardWriteError::FileNotFound {
+            path: path.to_string(),
+        })
     }
 }
 
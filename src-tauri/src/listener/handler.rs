@@ -1,28 +1,71 @@
-use crate::listener::image_utils;
+use crate::listener::selection::Selection;
+use crate::listener::state::LISTENER_STATE;
+use crate::listener::{hooks, image_utils, supervisor};
 use clipboard_rs::{Clipboard, ClipboardContext, ClipboardHandler, RustImageData};
 use clipboard_rs::common::RustImage;
 use log::{error, info};
 use serde_json::Map;
 use tauri::{AppHandle, Emitter};
+use std::time::Duration;
 use tauri_plugin_store::StoreExt;
 
+/**
+ * 本应用写入剪贴板后，在该时长内监听到的相同内容视为自己写入的
+ */
+const SELF_WRITE_WINDOW: Duration = Duration::from_secs(2);
+
+/**
+ * 读取变化后的剪贴板内容，不同的监听实现提供各自的读取方式
+ */
+pub trait ClipboardReader {
+    fn files(&self) -> Option<Vec<String>>;
+    fn text(&self) -> Option<String>;
+    fn image(&self) -> Option<RustImageData>;
+}
+
+impl ClipboardReader for ClipboardContext {
+    fn files(&self) -> Option<Vec<String>> {
+        self.get_files().ok()
+    }
+
+    fn text(&self) -> Option<String> {
+        self.get_text().ok()
+    }
+
+    fn image(&self) -> Option<RustImageData> {
+        self.get_image().ok()
+    }
+}
+
+/**
+ * 处理剪贴板变化，执行捕获钩子后通知前端
+ */
 pub struct ClipboardManager {
-    ctx: ClipboardContext,
     app: AppHandle,
 }
 
 impl ClipboardManager {
     pub fn new(app: AppHandle) -> Self {
-        let ctx = ClipboardContext::new().unwrap();
-        ClipboardManager { ctx, app }
+        ClipboardManager { app }
     }
-}
 
-impl ClipboardHandler for ClipboardManager {
-    fn on_clipboard_change(&mut self) {
-        if let Ok(files) = self.ctx.get_files() {
+    /**
+     * 处理一次选区变化，selection 为内容的来源选区
+     */
+    pub fn handle_change(&self, reader: &dyn ClipboardReader, selection: Selection) {
+        supervisor::record_event();
+        // 写入图片时可能同时写入图片文件，需要在读取文件之前判断
+        if selection == Selection::Clipboard {
+            let written_at = LISTENER_STATE.lock().unwrap().image_written_at.take();
+            if written_at.is_some_and(|at| at.elapsed() < SELF_WRITE_WINDOW) {
+                info!("剪贴板图片由本应用写入，忽略");
+                return;
+            }
+        }
+        if let Some(files) = reader.files() {
             info!("检测到剪贴板有复制到新的文件，files:{}", files.join(";"));
-            self.emit_clipboard_change("file", |map| {
+            // 文件元数据的采集可能很慢，由前端通过 get_file_metadata 在后台线程中采集
+            self.emit_clipboard_change("file", selection, |map| {
                 map.insert(
                     "file_path".into(),
                     serde_json::Value::String(serde_json::to_string(&files).unwrap()),
@@ -31,32 +74,81 @@ impl ClipboardHandler for ClipboardManager {
             return;
         }
 
-        if let Ok(text) = self.ctx.get_text() {
-            info!("检测到剪贴板有复制到新的文本，text:{}", text);
-            self.emit_clipboard_change("text", |map| {
+        if let Some(text) = reader.text() {
+            if selection == Selection::Clipboard && is_synced_text(&text) {
+                info!("剪贴板文本由 PRIMARY 同步写入，忽略");
+                return;
+            }
+            info!("检测到{}选区有新的文本，text:{}", selection.as_str(), text);
+            self.emit_clipboard_change("text", selection, |map| {
                 map.insert("content".into(), text.into());
             });
             return;
         }
 
-        if let Ok(image) = self.ctx.get_image() {
-            self.handle_image(image);
+        if let Some(image) = reader.image() {
+            self.handle_image(image, selection);
         }
     }
 }
 
+/**
+ * 判断剪贴板文本是否为本程序刚从 PRIMARY 同步写入的，同一次写入可能触发多次变化，所以不清除标记
+ */
+fn is_synced_text(text: &str) -> bool {
+    let state = LISTENER_STATE.lock().unwrap();
+    state
+        .synced_text
+        .as_ref()
+        .is_some_and(|(synced, at)| synced == text && at.elapsed() < SELF_WRITE_WINDOW)
+}
+
+/**
+ * clipboard-rs 监听使用的处理器，X11、Windows 和 macOS 下使用
+ */
+pub struct NativeHandler {
+    ctx: ClipboardContext,
+    manager: ClipboardManager,
+}
+
+impl NativeHandler {
+    pub fn new(app: AppHandle) -> Self {
+        let ctx = ClipboardContext::new().unwrap();
+        NativeHandler {
+            ctx,
+            manager: ClipboardManager::new(app),
+        }
+    }
+}
+
+impl ClipboardHandler for NativeHandler {
+    fn on_clipboard_change(&mut self) {
+        self.manager.handle_change(&self.ctx, Selection::Clipboard);
+    }
+}
+
 impl ClipboardManager {
-    fn emit_clipboard_change<F>(&self, clip_type: &str, extra: F)
+    /**
+     * 执行捕获钩子后发送剪贴板变化事件，返回 false 表示被钩子丢弃
+     */
+    fn emit_clipboard_change<F>(&self, clip_type: &str, selection: Selection, extra: F) -> bool
     where
         F: FnOnce(&mut Map<String, serde_json::Value>),
     {
         let mut map = Map::new();
         map.insert("type".into(), clip_type.into());
+        map.insert("source".into(), selection.as_str().into());
         extra(&mut map);
-        let _ = self.app.emit("clipboard-change", serde_json::json!(&map));
+        match hooks::run_capture_hooks(&self.app, map) {
+            Some(map) => {
+                let _ = self.app.emit("clipboard-change", serde_json::json!(&map));
+                true
+            }
+            None => false,
+        }
     }
 
-    fn handle_image(&self, image: RustImageData) {
+    fn handle_image(&self, image: RustImageData, selection: Selection) {
         let mut is_save_image = false;
         match self.app.store("settings.json") {
             Ok(store) => {
@@ -75,9 +167,12 @@ impl ClipboardManager {
         let path = image_utils::get_image_path(self.app.clone());
         if image.save_to_path(&path).is_ok() {
             info!("检测到剪贴板有复制到新的图片，image_path:{}", path);
-            self.emit_clipboard_change("image", |map| {
-                map.insert("file_path".into(), path.into());
+            let emitted = self.emit_clipboard_change("image", selection, |map| {
+                map.insert("file_path".into(), path.clone().into());
             });
+            if !emitted {
+                let _ = std::fs::remove_file(&path);
+            }
         } else {
             error!("图片保存失败，{}", path);
         }
@@ -0,0 +1,248 @@
+use crate::plugins::{self, PluginCall, PluginHost};
+use log::{info, warn};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+/**
+ * 插件处理捕获内容时调用的命令
+ */
+const PLUGIN_HOOK_COMMAND: &str = "capture";
+/**
+ * 插件处理捕获内容需要的权限
+ */
+const PLUGIN_HOOK_PERMISSION: &str = "clipboard:read";
+/**
+ * 插件钩子默认超时时长，单位：毫秒
+ */
+const DEFAULT_HOOK_TIMEOUT_MS: u64 = 1000;
+/**
+ * 一次捕获中所有插件钩子的总时长，超过后跳过剩余的插件钩子，避免阻塞剪贴板监听
+ */
+const HOOK_CHAIN_DEADLINE: Duration = Duration::from_secs(3);
+/**
+ * 链接中需要去掉的跟踪参数
+ */
+const TRACKING_PARAMS: [&str; 10] = [
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid",
+];
+
+/**
+ * 捕获钩子配置，按配置顺序依次执行
+ */
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct HookConfig {
+    /**
+     * 内置钩子名称，或者 plugin:<插件id>
+     */
+    id: String,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    /**
+     * 插件钩子超时时长，单位：毫秒
+     */
+    timeout_ms: Option<u64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/**
+ * 钩子处理结果
+ */
+enum HookOutcome {
+    /**
+     * 保留（可能已修改）的捕获内容
+     */
+    Keep(Map<String, Value>),
+    /**
+     * 丢弃本次捕获
+     */
+    Reject,
+}
+
+/**
+ * 依次执行捕获钩子，返回 None 表示本次捕获被丢弃
+ */
+pub fn run_capture_hooks(
+    app: &AppHandle,
+    capture: Map<String, Value>,
+) -> Option<Map<String, Value>> {
+    let mut capture = capture;
+    let deadline = Instant::now() + HOOK_CHAIN_DEADLINE;
+    for hook in load_hooks(app).into_iter().filter(|h| h.enabled) {
+        let outcome = match hook.id.strip_prefix("plugin:") {
+            Some(plugin_id) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    warn!("捕获钩子总时长超过限制，跳过插件钩子: {}", hook.id);
+                    continue;
+                }
+                run_plugin_hook(app, plugin_id, &hook, remaining, capture.clone())
+            }
+            None => run_builtin_hook(&hook.id, capture.clone()),
+        };
+        match outcome {
+            HookOutcome::Keep(next) => capture = next,
+            HookOutcome::Reject => {
+                info!("剪贴板内容被钩子丢弃: {}", hook.id);
+                return None;
+            }
+        }
+    }
+    Some(capture)
+}
+
+/**
+ * 从配置文件中读取捕获钩子
+ */
+fn load_hooks(app: &AppHandle) -> Vec<HookConfig> {
+    match app.store("settings.json") {
+        Ok(store) => store
+            .get("captureHooks")
+            .and_then(|v| serde_json::from_value::<Vec<HookConfig>>(v).ok())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/**
+ * 执行内置钩子，内置钩子只处理文本
+ */
+fn run_builtin_hook(id: &str, mut capture: Map<String, Value>) -> HookOutcome {
+    if capture.get("type").and_then(|v| v.as_str()) != Some("text") {
+        return HookOutcome::Keep(capture);
+    }
+    let text = match capture.get("content").and_then(|v| v.as_str()) {
+        Some(text) => text.to_string(),
+        None => return HookOutcome::Keep(capture),
+    };
+
+    let text = match id {
+        "stripTrackingParams" => strip_tracking_params(&text),
+        "trim" => {
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                return HookOutcome::Reject;
+            }
+            trimmed.to_string()
+        }
+        "normalizeLineEndings" => text.replace("\r\n", "\n").replace('\r', "\n"),
+        _ => {
+            warn!("未知的捕获钩子: {}", id);
+            text
+        }
+    };
+    capture.insert("content".into(), text.into());
+    HookOutcome::Keep(capture)
+}
+
+/**
+ * 执行插件钩子，插件返回 {"reject": true} 丢弃捕获，返回 {"content": "..."} 替换文本内容，
+ * 插件只能修改文本类型的内容，不能修改来源、文件信息等其它字段，
+ * 超时或出错时保留原内容，避免插件异常导致剪贴板记录丢失
+ */
+fn run_plugin_hook(
+    app: &AppHandle,
+    plugin_id: &str,
+    hook: &HookConfig,
+    remaining: Duration,
+    mut capture: Map<String, Value>,
+) -> HookOutcome {
+    if !plugins::load_approved(app, plugin_id)
+        .iter()
+        .any(|p| p == PLUGIN_HOOK_PERMISSION)
+    {
+        warn!(
+            "插件 {} 没有 {} 权限，跳过捕获钩子",
+            plugin_id, PLUGIN_HOOK_PERMISSION
+        );
+        return HookOutcome::Keep(capture);
+    }
+
+    // 单个插件的超时不能超过整个钩子链剩余的时间
+    let timeout_ms = hook
+        .timeout_ms
+        .unwrap_or(DEFAULT_HOOK_TIMEOUT_MS)
+        .min(remaining.as_millis() as u64)
+        .max(1);
+    let call = PluginCall {
+        plugin_id: plugin_id.to_string(),
+        method: PLUGIN_HOOK_COMMAND.to_string(),
+        params: Value::Object(capture.clone()),
+        call_id: None,
+        timeout_ms: Some(timeout_ms),
+    };
+    let host = app.state::<PluginHost>();
+    let result = tauri::async_runtime::block_on(host.call(app, call));
+
+    let value = match result {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("插件 {} 捕获钩子执行失败: {}", plugin_id, e);
+            return HookOutcome::Keep(capture);
+        }
+    };
+    if value.get("reject").and_then(|v| v.as_bool()) == Some(true) {
+        return HookOutcome::Reject;
+    }
+    let content = match value.get("content") {
+        Some(Value::String(content)) => content.clone(),
+        Some(_) => {
+            warn!("插件 {} 返回的内容不是文本，已忽略", plugin_id);
+            return HookOutcome::Keep(capture);
+        }
+        None => return HookOutcome::Keep(capture),
+    };
+    if capture.get("type").and_then(|v| v.as_str()) != Some("text") {
+        warn!("插件 {} 只能修改文本内容，已忽略", plugin_id);
+        return HookOutcome::Keep(capture);
+    }
+    capture.insert("content".into(), content.into());
+    HookOutcome::Keep(capture)
+}
+
+/**
+ * 去掉链接中的跟踪参数，只处理整段文本是一个链接的情况
+ */
+fn strip_tracking_params(text: &str) -> String {
+    let url = text.trim();
+    let is_url = (url.starts_with("http://") || url.starts_with("https://"))
+        && !url.chars().any(char::is_whitespace);
+    if !is_url {
+        return text.to_string();
+    }
+
+    let (without_fragment, fragment) = match url.split_once('#') {
+        Some((base, fragment)) => (base, Some(fragment)),
+        None => (url, None),
+    };
+    let (base, query) = match without_fragment.split_once('?') {
+        Some((base, query)) => (base, query),
+        None => return text.to_string(),
+    };
+
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default().to_lowercase();
+            !pair.is_empty() && !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+        })
+        .collect();
+
+    let mut result = base.to_string();
+    if !kept.is_empty() {
+        result.push('?');
+        result.push_str(&kept.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+    result
+}
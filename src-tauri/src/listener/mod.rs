@@ -1,16 +1,26 @@
 mod handler;
+mod hooks;
 mod state;
 mod image_utils;
 mod clipboard_ops;
+mod pause;
+mod selection;
+mod supervisor;
+#[cfg(target_os = "linux")]
+mod wayland;
+#[cfg(target_os = "linux")]
+mod x11_selection;
 
-use crate::listener::handler::ClipboardManager;
-use crate::listener::state::LISTENER_STATE;
-use log::info;
+use crate::listener::state::{ListenerState, LISTENER_STATE};
+use log::{info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread;
-use clipboard_rs::ClipboardWatcher;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
 pub use clipboard_ops::*;
+pub use pause::*;
+pub use supervisor::get_listener_health;
 
 pub fn start_listening(app: AppHandle) {
     let mut state = LISTENER_STATE.lock().unwrap();
@@ -20,19 +30,12 @@ pub fn start_listening(app: AppHandle) {
         return;
     }
 
-    let manager = ClipboardManager::new(app.clone());
-    let mut watcher = clipboard_rs::ClipboardWatcherContext::new().unwrap();
-    let shutdown = watcher.add_handler(manager).get_shutdown_channel();
-
-    let handle = thread::spawn(move || {
-        info!("开始监听剪贴板变化...");
-        watcher.start_watch();
-    });
-
-    state.watcher_shutdown = Some(shutdown);
-    state.watcher_thread = Some(handle);
-    state.listen_status = true;
-    app.clone().emit("update-listening", true).unwrap();
+    spawn_watcher(&app, &mut state);
+    // 恢复监听后取消暂停
+    state.pause = None;
+    state.pause_generation += 1;
+    drop(state);
+    notify_changed(&app);
 
     info!("已开始监听剪贴板");
 }
@@ -40,15 +43,77 @@ pub fn start_listening(app: AppHandle) {
 pub fn stop_listening(app: AppHandle) {
     let mut state = LISTENER_STATE.lock().unwrap();
 
-    if !state.listen_status {
+    if !state.listen_status && state.pause.is_none() {
         info!("当前没有监听，不需要停止监听");
         return;
     }
 
-    state.watcher_shutdown.take().unwrap().stop();
-    drop(state.watcher_thread.take());
-    state.listen_status = false;
-    app.clone().emit("update-listening", false).unwrap();
+    let handle = stop_watcher(&mut state);
+    state.pause = None;
+    state.pause_generation += 1;
+    drop(state);
+    join_watcher(handle);
+    notify_changed(&app);
 
     info!("已停止监听剪贴板");
 }
+
+/**
+ * 重新启动监听线程，使 PRIMARY 选区相关设置生效，暂停或停止监听时不需要处理
+ */
+#[tauri::command]
+pub fn restart_clipboard_listening(app: AppHandle) {
+    let handle = {
+        let mut state = LISTENER_STATE.lock().unwrap();
+        if !state.listen_status {
+            return;
+        }
+        stop_watcher(&mut state)
+    };
+    join_watcher(handle);
+
+    let mut state = LISTENER_STATE.lock().unwrap();
+    // 等待期间可能已经被暂停或重新启动
+    if state.listen_status || state.pause.is_some() {
+        return;
+    }
+    spawn_watcher(&app, &mut state);
+    info!("已重新启动剪贴板监听");
+}
+
+/**
+ * 启动监听守护线程
+ */
+fn spawn_watcher(app: &AppHandle, state: &mut ListenerState) {
+    let stop = Arc::new(AtomicBool::new(false));
+    let handle = supervisor::spawn_supervised(app.clone(), stop.clone());
+
+    state.watcher_stop = Some(stop);
+    state.watcher_thread = Some(handle);
+    state.listen_status = true;
+}
+
+/**
+ * 通知监听线程停止，返回的线程需要在释放锁之后再等待结束
+ */
+fn stop_watcher(state: &mut ListenerState) -> Option<thread::JoinHandle<()>> {
+    if let Some(stop) = state.watcher_stop.take() {
+        stop.store(true, Ordering::SeqCst);
+    }
+    if let Some(shutdown) = state.watcher_shutdown.take() {
+        shutdown.stop();
+    }
+    state.listen_status = false;
+    state.watcher_thread.take()
+}
+
+/**
+ * 等待监听线程结束，不能在持有 LISTENER_STATE 锁时调用
+ */
+fn join_watcher(handle: Option<thread::JoinHandle<()>>) {
+    if let Some(handle) = handle {
+        if handle.join().is_err() {
+            warn!("剪贴板监听线程异常结束");
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::listener::state::{Pause, LISTENER_STATE};
+use crate::listener::{join_watcher, start_listening, stop_watcher};
+use crate::tray;
+use log::{error, info};
+use serde::Serialize;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+/**
+ * 暂停期间更新托盘倒计时的间隔
+ */
+const COUNTDOWN_INTERVAL: Duration = Duration::from_secs(30);
+
+/**
+ * 剪贴板监听状态
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "status",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ListeningStatus {
+    Listening,
+    Stopped,
+    /**
+     * 暂停中，resume_at 为自动恢复的时间戳（毫秒），为空表示暂停到程序重启
+     */
+    Paused {
+        resume_at: Option<u64>,
+        remaining_secs: Option<u64>,
+    },
+}
+
+/**
+ * 获取当前的监听状态
+ */
+pub fn listening_status() -> ListeningStatus {
+    let state = LISTENER_STATE.lock().unwrap();
+    if state.listen_status {
+        return ListeningStatus::Listening;
+    }
+    match state.pause {
+        Some(pause) => ListeningStatus::Paused {
+            resume_at: pause.resume_at,
+            remaining_secs: pause
+                .until
+                .map(|until| until.saturating_duration_since(Instant::now()).as_secs()),
+        },
+        None => ListeningStatus::Stopped,
+    }
+}
+
+/**
+ * 暂停监听，duration 为空时暂停到程序重启，到时间后自动恢复
+ */
+pub fn pause_listening(app: AppHandle, duration: Option<Duration>) {
+    let (generation, handle) = {
+        let mut state = LISTENER_STATE.lock().unwrap();
+        let handle = stop_watcher(&mut state);
+        let resume_at = duration.map(|duration| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            (now + duration).as_millis() as u64
+        });
+        state.pause = Some(Pause {
+            until: duration.map(|duration| Instant::now() + duration),
+            resume_at,
+        });
+        state.pause_generation += 1;
+        (state.pause_generation, handle)
+    };
+    join_watcher(handle);
+    match duration {
+        Some(duration) => info!("暂停监听剪贴板 {} 秒", duration.as_secs()),
+        None => info!("暂停监听剪贴板，直到程序重启"),
+    }
+    notify_changed(&app);
+
+    if duration.is_some() {
+        tauri::async_runtime::spawn(resume_when_due(app, generation));
+    }
+}
+
+/**
+ * 暂停到期后恢复监听，期间定时更新托盘上的倒计时
+ */
+async fn resume_when_due(app: AppHandle, generation: u64) {
+    loop {
+        let remaining = {
+            let state = LISTENER_STATE.lock().unwrap();
+            if state.pause_generation != generation {
+                // 已经手动恢复或重新暂停
+                return;
+            }
+            match state.pause.and_then(|pause| pause.until) {
+                Some(until) => until.saturating_duration_since(Instant::now()),
+                None => return,
+            }
+        };
+        if remaining.is_zero() {
+            info!("暂停结束，恢复监听剪贴板");
+            start_listening(app);
+            return;
+        }
+        tokio::time::sleep(remaining.min(COUNTDOWN_INTERVAL)).await;
+        if LISTENER_STATE.lock().unwrap().pause_generation == generation {
+            if let Err(e) = tray::reload_tray_menu(app.clone()) {
+                error!("更新托盘暂停倒计时失败: {}", e);
+            }
+        }
+    }
+}
+
+/**
+ * 监听状态变化后更新托盘并通知前端
+ */
+pub fn notify_changed(app: &AppHandle) {
+    if let Err(e) = tray::reload_tray_menu(app.clone()) {
+        error!("更新托盘监听状态失败: {}", e);
+    }
+    let _ = app.emit("listening-status-change", listening_status());
+}
+
+/**
+ * 暂停监听，minutes 为空时暂停到程序重启
+ */
+#[tauri::command]
+pub fn pause_clipboard_listening(app: AppHandle, minutes: Option<u64>) {
+    pause_listening(
+        app,
+        minutes.map(|minutes| Duration::from_secs(minutes * 60)),
+    );
+}
+
+/**
+ * 获取监听状态
+ */
+#[tauri::command]
+pub fn get_listening_status() -> ListeningStatus {
+    listening_status()
+}
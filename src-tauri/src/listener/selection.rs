@@ -0,0 +1,133 @@
+// PRIMARY 选区只在 Linux 下监听，其他平台只用到 Selection
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+
+use crate::listener::handler::ClipboardReader;
+use clipboard_rs::RustImageData;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+/**
+ * PRIMARY 选区停止变化超过该时长后才读取，避免拖动选择时频繁记录
+ */
+const PRIMARY_DEBOUNCE: Duration = Duration::from_millis(600);
+
+/**
+ * 剪贴板内容的来源选区
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Selection {
+    /**
+     * 复制（Ctrl+C）使用的 CLIPBOARD 选区
+     */
+    Clipboard,
+    /**
+     * 选中文本即写入、中键粘贴使用的 PRIMARY 选区，只在 Linux 下存在
+     */
+    Primary,
+}
+
+impl Selection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Selection::Clipboard => "clipboard",
+            Selection::Primary => "primary",
+        }
+    }
+}
+
+/**
+ * PRIMARY 选区相关设置
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SelectionOptions {
+    /**
+     * 记录 PRIMARY 选区的内容
+     */
+    pub capture_primary: bool,
+    /**
+     * 保持 PRIMARY 和 CLIPBOARD 选区的文本一致
+     */
+    pub sync: bool,
+}
+
+impl SelectionOptions {
+    /**
+     * 从设置中读取，监听启动时读取一次
+     */
+    pub fn load(app: &AppHandle) -> Self {
+        let store = match app.store("settings.json") {
+            Ok(store) => store,
+            Err(_) => return Self::default(),
+        };
+        let flag = |key: &str| store.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        SelectionOptions {
+            capture_primary: flag("capturePrimarySelection"),
+            sync: flag("syncSelections"),
+        }
+    }
+
+    /**
+     * 是否需要关注 PRIMARY 选区
+     */
+    pub fn watch_primary(&self) -> bool {
+        self.capture_primary || self.sync
+    }
+}
+
+/**
+ * PRIMARY 选区的去抖，记录最后一次变化的时间
+ */
+#[derive(Default)]
+pub struct Debounce {
+    changed_at: Option<Instant>,
+}
+
+impl Debounce {
+    pub fn touch(&mut self) {
+        self.changed_at = Some(Instant::now());
+    }
+
+    /**
+     * 距离可以读取还需等待的时长，没有等待中的变化时返回 None
+     */
+    pub fn remaining(&self) -> Option<Duration> {
+        self.changed_at
+            .map(|at| PRIMARY_DEBOUNCE.saturating_sub(at.elapsed()))
+    }
+
+    /**
+     * 选区已经稳定时返回 true，并清除等待状态
+     */
+    pub fn take_due(&mut self) -> bool {
+        if self
+            .remaining()
+            .is_some_and(|remaining| remaining.is_zero())
+        {
+            self.changed_at = None;
+            return true;
+        }
+        false
+    }
+}
+
+/**
+ * 只包含文本的剪贴板内容，PRIMARY 选区只记录文本
+ */
+pub struct TextReader(pub String);
+
+impl ClipboardReader for TextReader {
+    fn files(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    fn text(&self) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    fn image(&self) -> Option<RustImageData> {
+        None
+    }
+}
@@ -1,22 +1,92 @@
 use clipboard_rs::WatcherShutdown;
 use lazy_static::lazy_static;
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Instant;
 
 #[derive(Default)]
 pub struct ListenerState {
     /**
-     * 监听线程
+     * 监听守护线程，监听异常退出时负责重启
      */
     pub watcher_thread: Option<thread::JoinHandle<()>>,
     /**
      * 监听关闭
      */
     pub watcher_shutdown: Option<WatcherShutdown>,
+    /**
+     * 停止标记，设置后守护线程不再重启监听
+     */
+    pub watcher_stop: Option<Arc<AtomicBool>>,
+    /**
+     * 监听运行状况
+     */
+    pub health: ListenerHealth,
     /**
      * 监听状态
      */
     pub listen_status: bool,
+    /**
+     * 最近一次由本应用写入图片的时间
+     */
+    pub image_written_at: Option<Instant>,
+    /**
+     * X11 下把 PRIMARY 同步到 CLIPBOARD 时写入的文本及写入时间
+     */
+    pub synced_text: Option<(String, Instant)>,
+    /**
+     * 暂停监听的信息，没有暂停时为空
+     */
+    pub pause: Option<Pause>,
+    /**
+     * 每次暂停或恢复时递增，用于让过期的恢复定时任务失效
+     */
+    pub pause_generation: u64,
+}
+
+/**
+ * 暂停监听
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Pause {
+    /**
+     * 自动恢复的时间，为空表示暂停到程序重启
+     */
+    pub until: Option<Instant>,
+    /**
+     * 自动恢复的时间戳，单位：毫秒，用于前端显示
+     */
+    pub resume_at: Option<u64>,
+}
+
+/**
+ * 监听运行状况
+ */
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerHealth {
+    /**
+     * 监听是否正在运行，异常退出等待重启时为 false
+     */
+    pub running: bool,
+    /**
+     * 当前使用的监听实现，native 为 clipboard-rs，wayland 为 data-control 协议
+     */
+    pub backend: Option<&'static str>,
+    /**
+     * 最近一次检测到剪贴板变化的时间戳，单位：毫秒
+     */
+    pub last_event_at: Option<u64>,
+    /**
+     * 监听异常退出后重启的次数
+     */
+    pub restart_count: u32,
+    /**
+     * 最近一次异常退出的原因
+     */
+    pub last_error: Option<String>,
 }
 
 lazy_static! {
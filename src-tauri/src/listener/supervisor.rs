@@ -0,0 +1,258 @@
+#[cfg(target_os = "linux")]
+use crate::listener::handler::ClipboardManager;
+use crate::listener::handler::NativeHandler;
+use crate::listener::selection::SelectionOptions;
+use crate::listener::state::{ListenerHealth, LISTENER_STATE};
+#[cfg(target_os = "linux")]
+use crate::listener::wayland::{self, WaylandWatcher};
+#[cfg(target_os = "linux")]
+use crate::listener::x11_selection::X11SelectionWatcher;
+use clipboard_rs::{ClipboardWatcher, ClipboardWatcherContext, WatcherShutdown};
+use log::{error, info, warn};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+/**
+ * 第一次重启前的等待时长，之后每次翻倍
+ */
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/**
+ * 重启前的最长等待时长
+ */
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/**
+ * 监听稳定运行超过该时长后，下次重启重新从最短等待时长开始
+ */
+const STABLE_RUN: Duration = Duration::from_secs(60);
+/**
+ * 等待重启期间检查停止标记的间隔
+ */
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/**
+ * 启动监听守护线程，监听退出或 panic 后按退避时长重启，直到设置停止标记
+ */
+pub fn spawn_supervised(app: AppHandle, stop: Arc<AtomicBool>) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            let started_at = Instant::now();
+            let failure = match run_watcher(&app, &stop) {
+                Some(failure) => failure,
+                // 收到停止标记，正常退出
+                None => break,
+            };
+
+            {
+                let mut state = LISTENER_STATE.lock().unwrap();
+                state.health.running = false;
+                state.health.restart_count += 1;
+                state.health.last_error = Some(failure.clone());
+            }
+            if started_at.elapsed() >= STABLE_RUN {
+                backoff = INITIAL_BACKOFF;
+            }
+            warn!(
+                "剪贴板监听异常退出: {}，{} 秒后重启",
+                failure,
+                backoff.as_secs()
+            );
+            if !wait_or_stop(&stop, backoff) {
+                break;
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+        LISTENER_STATE.lock().unwrap().health.running = false;
+        info!("剪贴板监听守护线程已退出");
+    })
+}
+
+/**
+ * 创建并运行一次监听，正常停止时返回 None，异常退出时返回原因
+ */
+fn run_watcher(app: &AppHandle, stop: &Arc<AtomicBool>) -> Option<String> {
+    let options = SelectionOptions::load(app);
+    // Wayland 下优先使用 data-control 协议，混成器不支持时回退到 clipboard-rs（通过 XWayland）
+    #[cfg(target_os = "linux")]
+    if wayland::is_wayland_session() {
+        match WaylandWatcher::connect(options) {
+            Ok(watcher) => return run_wayland(app, stop, watcher),
+            Err(e) => warn!("无法使用 Wayland 监听剪贴板，改用 X11 监听: {}", e),
+        }
+    }
+    run_native(app, stop, options)
+}
+
+/**
+ * 使用 clipboard-rs 监听
+ */
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn run_native(
+    app: &AppHandle,
+    stop: &Arc<AtomicBool>,
+    options: SelectionOptions,
+) -> Option<String> {
+    let mut watcher = match ClipboardWatcherContext::new() {
+        Ok(watcher) => watcher,
+        Err(e) => return Some(format!("创建剪贴板监听失败: {}", e)),
+    };
+    // 创建剪贴板上下文失败时会 panic，同样按异常退出处理
+    let handler = panic::catch_unwind(AssertUnwindSafe(|| NativeHandler::new(app.clone())));
+    let handler = match handler {
+        Ok(handler) => handler,
+        Err(payload) => {
+            return Some(format!(
+                "创建剪贴板上下文失败: {}",
+                panic_message(&*payload)
+            ));
+        }
+    };
+    let shutdown = watcher.add_handler(handler).get_shutdown_channel();
+    if !mark_running(stop, "native", Some(shutdown)) {
+        return None;
+    }
+
+    #[cfg(target_os = "linux")]
+    let done = Arc::new(AtomicBool::new(false));
+    #[cfg(target_os = "linux")]
+    let primary = options
+        .watch_primary()
+        .then(|| spawn_x11_selection(app.clone(), stop.clone(), done.clone(), options));
+
+    info!("开始监听剪贴板变化...");
+    let result = panic::catch_unwind(AssertUnwindSafe(|| watcher.start_watch()));
+
+    #[cfg(target_os = "linux")]
+    {
+        done.store(true, Ordering::SeqCst);
+        if let Some(handle) = primary {
+            let _ = handle.join();
+        }
+    }
+    exit_reason(stop, result.map(Ok))
+}
+
+/**
+ * X11 下 clipboard-rs 只监听 CLIPBOARD，PRIMARY 选区由单独的线程监听，随 CLIPBOARD 监听一起结束
+ */
+#[cfg(target_os = "linux")]
+fn spawn_x11_selection(
+    app: AppHandle,
+    stop: Arc<AtomicBool>,
+    done: Arc<AtomicBool>,
+    options: SelectionOptions,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut watcher = match X11SelectionWatcher::connect(options) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                warn!("无法监听 PRIMARY 选区: {}", e);
+                return;
+            }
+        };
+        let manager = ClipboardManager::new(app);
+        if let Err(e) = watcher.watch(&manager, &stop, &done) {
+            warn!("PRIMARY 选区监听异常退出: {}", e);
+        }
+    })
+}
+
+/**
+ * 使用 Wayland data-control 协议监听，停止时由监听循环检查停止标记
+ */
+#[cfg(target_os = "linux")]
+fn run_wayland(
+    app: &AppHandle,
+    stop: &Arc<AtomicBool>,
+    mut watcher: WaylandWatcher,
+) -> Option<String> {
+    if !mark_running(stop, "wayland", None) {
+        return None;
+    }
+    let manager = ClipboardManager::new(app.clone());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| watcher.watch(&manager, stop)));
+    exit_reason(stop, result)
+}
+
+/**
+ * 记录监听已开始，已经设置停止标记时返回 false
+ */
+fn mark_running(
+    stop: &AtomicBool,
+    backend: &'static str,
+    shutdown: Option<WatcherShutdown>,
+) -> bool {
+    // 和停止监听使用同一把锁，保证停止时能拿到当前的关闭通道
+    let mut state = LISTENER_STATE.lock().unwrap();
+    if stop.load(Ordering::SeqCst) {
+        return false;
+    }
+    state.watcher_shutdown = shutdown;
+    state.health.running = true;
+    state.health.backend = Some(backend);
+    true
+}
+
+/**
+ * 监听结束的原因，收到停止标记时返回 None
+ */
+fn exit_reason(stop: &AtomicBool, result: thread::Result<Result<(), String>>) -> Option<String> {
+    if stop.load(Ordering::SeqCst) {
+        return None;
+    }
+    match result {
+        Ok(Ok(())) => Some("监听意外结束".to_string()),
+        Ok(Err(e)) => Some(e),
+        Err(payload) => {
+            let message = panic_message(&*payload);
+            error!("剪贴板监听线程 panic: {}", message);
+            Some(format!("监听线程 panic: {}", message))
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "未知错误".to_string())
+}
+
+/**
+ * 等待指定时长，期间收到停止标记时返回 false
+ */
+fn wait_or_stop(stop: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        thread::sleep(STOP_CHECK_INTERVAL);
+    }
+    !stop.load(Ordering::SeqCst)
+}
+
+/**
+ * 记录检测到剪贴板变化的时间
+ */
+pub fn record_event() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    LISTENER_STATE.lock().unwrap().health.last_event_at = Some(now);
+}
+
+/**
+ * 获取监听运行状况
+ */
+#[tauri::command]
+pub fn get_listener_health() -> ListenerHealth {
+    LISTENER_STATE.lock().unwrap().health.clone()
+}
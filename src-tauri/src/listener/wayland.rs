@@ -0,0 +1,669 @@
+use crate::listener::handler::{ClipboardManager, ClipboardReader};
+use crate::listener::selection::{Debounce, Selection, SelectionOptions, TextReader};
+use clipboard_rs::common::RustImage;
+use clipboard_rs::RustImageData;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use wayland_client::backend::{ObjectId, WaylandError};
+use wayland_client::globals::{registry_queue_init, GlobalList, GlobalListContents};
+use wayland_client::protocol::wl_registry::WlRegistry;
+use wayland_client::protocol::wl_seat::WlSeat;
+use wayland_client::{event_created_child, Connection, Dispatch, EventQueue, Proxy, QueueHandle};
+use wayland_protocols::ext::data_control::v1::client::{
+    ext_data_control_device_v1::{self, ExtDataControlDeviceV1},
+    ext_data_control_manager_v1::{self, ExtDataControlManagerV1},
+    ext_data_control_offer_v1::{self, ExtDataControlOfferV1},
+    ext_data_control_source_v1::{self, ExtDataControlSourceV1},
+};
+use wayland_protocols_wlr::data_control::v1::client::{
+    zwlr_data_control_device_v1::{self, ZwlrDataControlDeviceV1},
+    zwlr_data_control_manager_v1::{self, ZwlrDataControlManagerV1},
+    zwlr_data_control_offer_v1::{self, ZwlrDataControlOfferV1},
+    zwlr_data_control_source_v1::{self, ZwlrDataControlSourceV1},
+};
+
+/**
+ * 等待 Wayland 事件时检查停止标记的间隔
+ */
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/**
+ * 读取剪贴板内容的超时时间，复制内容的程序没有响应时放弃本次读取
+ */
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+const URI_LIST_MIME: &str = "text/uri-list";
+const TEXT_MIMES: [&str; 5] = [
+    "text/plain;charset=utf-8",
+    "UTF8_STRING",
+    "text/plain",
+    "STRING",
+    "TEXT",
+];
+const IMAGE_MIMES: [&str; 3] = ["image/png", "image/jpeg", "image/bmp"];
+
+/**
+ * 当前会话是否为 Wayland
+ */
+pub fn is_wayland_session() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/**
+ * 基于 data-control 协议的剪贴板监听，优先使用 ext-data-control，不支持时使用 wlr-data-control
+ */
+pub struct WaylandWatcher {
+    conn: Connection,
+    queue: EventQueue<WaylandState>,
+    state: WaylandState,
+    device: Device,
+    options: SelectionOptions,
+    last_primary: Option<String>,
+}
+
+impl WaylandWatcher {
+    /**
+     * 连接 Wayland 并创建数据设备，混成器不支持 data-control 协议时返回错误
+     */
+    pub fn connect(options: SelectionOptions) -> Result<Self, String> {
+        let conn = Connection::connect_to_env().map_err(|e| format!("连接 Wayland 失败: {}", e))?;
+        let (globals, mut queue) = registry_queue_init::<WaylandState>(&conn)
+            .map_err(|e| format!("获取 Wayland 全局对象失败: {}", e))?;
+        let qh = queue.handle();
+
+        let seat: WlSeat = globals
+            .bind(&qh, 1..=8, ())
+            .map_err(|e| format!("没有可用的 wl_seat: {}", e))?;
+        let device = Device::create(&globals, &qh, &seat)?;
+
+        let mut state = WaylandState {
+            watch_primary: options.watch_primary(),
+            ..Default::default()
+        };
+        // 创建设备后混成器会立即发送当前的选区内容，不属于新的变化
+        queue
+            .roundtrip(&mut state)
+            .map_err(|e| format!("初始化 Wayland 剪贴板失败: {}", e))?;
+        for selection in [Selection::Clipboard, Selection::Primary] {
+            state.set_offer(selection, None);
+        }
+        state.debounce = Debounce::default();
+
+        info!("使用 Wayland {} 协议监听剪贴板", device.protocol());
+        Ok(WaylandWatcher {
+            conn,
+            queue,
+            state,
+            device,
+            options,
+            last_primary: None,
+        })
+    }
+
+    /**
+     * 阻塞监听剪贴板变化，直到设置停止标记，连接异常时返回错误
+     */
+    pub fn watch(&mut self, manager: &ClipboardManager, stop: &AtomicBool) -> Result<(), String> {
+        info!("开始监听 Wayland 剪贴板变化...");
+        let result = self.watch_loop(manager, stop);
+        self.device.destroy();
+        let _ = self.conn.flush();
+        result
+    }
+
+    fn watch_loop(&mut self, manager: &ClipboardManager, stop: &AtomicBool) -> Result<(), String> {
+        while !stop.load(Ordering::SeqCst) {
+            self.wait_events()?;
+            if self.state.finished {
+                return Err("Wayland 数据设备已失效".to_string());
+            }
+            if let Some(offer) = self.state.clipboard.take() {
+                let snapshot = self.read_offer(&offer);
+                self.state.discard(offer);
+                if let Some(snapshot) = snapshot {
+                    if self.options.sync {
+                        if let Some(text) = snapshot.text.clone() {
+                            self.set_selection(Selection::Primary, text);
+                        }
+                    }
+                    manager.handle_change(&snapshot, Selection::Clipboard);
+                }
+            }
+            if self.state.debounce.take_due() {
+                if let Some(offer) = self.state.primary.take() {
+                    let text = self.read_text(&offer);
+                    self.state.discard(offer);
+                    if let Some(text) = text {
+                        self.primary_settled(manager, text);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * PRIMARY 选区停止变化后记录并同步
+     */
+    fn primary_settled(&mut self, manager: &ClipboardManager, text: String) {
+        if text.trim().is_empty() || self.last_primary.as_ref() == Some(&text) {
+            return;
+        }
+        self.last_primary = Some(text.clone());
+        if self.options.sync {
+            self.set_selection(Selection::Clipboard, text.clone());
+        }
+        if self.options.capture_primary {
+            manager.handle_change(&TextReader(text), Selection::Primary);
+        }
+    }
+
+    /**
+     * 由本程序提供选区内容，混成器随后发送的该选区变化不再处理
+     */
+    fn set_selection(&mut self, selection: Selection, text: String) {
+        let qh = self.queue.handle();
+        if self.device.set(selection, text, &qh) {
+            self.state.ignore_next(selection);
+        }
+    }
+
+    /**
+     * 等待并分发 Wayland 事件，最多等待 POLL_INTERVAL，以便及时响应停止标记
+     */
+    fn wait_events(&mut self) -> Result<(), String> {
+        let dispatch_error = |e| format!("分发 Wayland 事件失败: {}", e);
+        self.queue
+            .dispatch_pending(&mut self.state)
+            .map_err(dispatch_error)?;
+        self.queue
+            .flush()
+            .map_err(|e| format!("发送 Wayland 请求失败: {}", e))?;
+
+        let timeout = self
+            .state
+            .debounce
+            .remaining()
+            .map_or(POLL_INTERVAL, |remaining| remaining.min(POLL_INTERVAL));
+        if let Some(guard) = self.queue.prepare_read() {
+            if poll_readable(guard.connection_fd(), timeout) {
+                match guard.read() {
+                    Ok(_) => {}
+                    Err(WaylandError::Io(e)) if e.kind() == ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(format!("读取 Wayland 事件失败: {}", e)),
+                }
+            }
+        }
+        self.queue
+            .dispatch_pending(&mut self.state)
+            .map_err(dispatch_error)?;
+        Ok(())
+    }
+
+    /**
+     * 按文件、文本、图片的顺序读取剪贴板内容，和 clipboard-rs 监听的处理顺序保持一致
+     */
+    fn read_offer(&self, offer: &Offer) -> Option<WaylandSnapshot> {
+        let mimes = self.state.offers.get(&offer.id())?;
+        let mut snapshot = WaylandSnapshot::default();
+
+        if mimes.iter().any(|mime| mime == URI_LIST_MIME) {
+            snapshot.files = self
+                .receive(offer, URI_LIST_MIME)
+                .map(|data| parse_uri_list(&String::from_utf8_lossy(&data)))
+                .filter(|files| !files.is_empty());
+            if snapshot.files.is_some() {
+                return Some(snapshot);
+            }
+        }
+
+        snapshot.text = self.read_text(offer);
+        if snapshot.text.is_some() {
+            return Some(snapshot);
+        }
+
+        if let Some(mime) = IMAGE_MIMES.iter().find(|t| mimes.iter().any(|m| m == *t)) {
+            snapshot.image = self.receive(offer, mime);
+        }
+        Some(snapshot)
+    }
+
+    fn read_text(&self, offer: &Offer) -> Option<String> {
+        let mimes = self.state.offers.get(&offer.id())?;
+        let mime = TEXT_MIMES.iter().find(|t| mimes.iter().any(|m| m == *t))?;
+        self.receive(offer, mime)
+            .map(|data| String::from_utf8_lossy(&data).into_owned())
+    }
+
+    /**
+     * 通过管道接收指定格式的剪贴板内容
+     */
+    fn receive(&self, offer: &Offer, mime: &str) -> Option<Vec<u8>> {
+        let (read, write) = match pipe() {
+            Ok(pipe) => pipe,
+            Err(e) => {
+                warn!("创建管道失败: {}", e);
+                return None;
+            }
+        };
+        offer.receive(mime.to_string(), write.as_fd());
+        if let Err(e) = self.conn.flush() {
+            warn!("发送 Wayland 请求失败: {}", e);
+            return None;
+        }
+        // 关闭本进程持有的写端，复制内容的程序写完后才能读到结束
+        drop(write);
+
+        let mut file = File::from(read);
+        let mut data = Vec::new();
+        let mut buf = [0u8; 8192];
+        let deadline = Instant::now() + READ_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || !poll_readable(file.as_fd(), remaining) {
+                warn!("读取剪贴板内容超时，mime:{}", mime);
+                return None;
+            }
+            match file.read(&mut buf) {
+                Ok(0) => return Some(data),
+                Ok(n) => data.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => {
+                    warn!("读取剪贴板内容失败，mime:{}，{}", mime, e);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/**
+ * 从 Wayland 读取到的剪贴板内容
+ */
+#[derive(Default)]
+struct WaylandSnapshot {
+    files: Option<Vec<String>>,
+    text: Option<String>,
+    /**
+     * 图片的原始数据，处理时再解码
+     */
+    image: Option<Vec<u8>>,
+}
+
+impl ClipboardReader for WaylandSnapshot {
+    fn files(&self) -> Option<Vec<String>> {
+        self.files.clone()
+    }
+
+    fn text(&self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn image(&self) -> Option<RustImageData> {
+        let data = self.image.as_ref()?;
+        RustImageData::from_bytes(data).ok()
+    }
+}
+
+#[derive(Default)]
+struct WaylandState {
+    /**
+     * 混成器提供的剪贴板内容及其支持的格式
+     */
+    offers: HashMap<ObjectId, Vec<String>>,
+    /**
+     * 新的 CLIPBOARD 选区内容，等待读取
+     */
+    clipboard: Option<Offer>,
+    /**
+     * 新的 PRIMARY 选区内容，稳定后读取
+     */
+    primary: Option<Offer>,
+    watch_primary: bool,
+    debounce: Debounce,
+    /**
+     * 本程序刚设置过的选区，下一次变化来自本程序，不需要处理
+     */
+    ignored: Vec<Selection>,
+    /**
+     * 数据设备已失效，需要重新创建
+     */
+    finished: bool,
+}
+
+impl WaylandState {
+    fn set_offer(&mut self, selection: Selection, offer: Option<Offer>) {
+        // 还没来得及读取的旧内容已经失效
+        let old = match selection {
+            Selection::Clipboard => self.clipboard.take(),
+            Selection::Primary => self.primary.take(),
+        };
+        if let Some(old) = old {
+            self.discard(old);
+        }
+        let ignored = match self.ignored.iter().position(|s| *s == selection) {
+            Some(index) => {
+                self.ignored.remove(index);
+                true
+            }
+            None => false,
+        };
+        let offer = match offer {
+            Some(offer) if ignored || (selection == Selection::Primary && !self.watch_primary) => {
+                self.discard(offer);
+                return;
+            }
+            Some(offer) => offer,
+            None => return,
+        };
+        match selection {
+            Selection::Clipboard => self.clipboard = Some(offer),
+            Selection::Primary => {
+                self.primary = Some(offer);
+                self.debounce.touch();
+            }
+        }
+    }
+
+    fn ignore_next(&mut self, selection: Selection) {
+        self.ignored.push(selection);
+    }
+
+    fn discard(&mut self, offer: Offer) {
+        self.offers.remove(&offer.id());
+        offer.destroy();
+    }
+}
+
+/**
+ * 两种 data-control 协议的数据设备
+ */
+enum Device {
+    Ext(ExtDataControlManagerV1, ExtDataControlDeviceV1),
+    Wlr(ZwlrDataControlManagerV1, ZwlrDataControlDeviceV1),
+}
+
+impl Device {
+    fn create(
+        globals: &GlobalList,
+        qh: &QueueHandle<WaylandState>,
+        seat: &WlSeat,
+    ) -> Result<Self, String> {
+        if let Ok(manager) = globals.bind::<ExtDataControlManagerV1, _, _>(qh, 1..=1, ()) {
+            let device = manager.get_data_device(seat, qh, ());
+            return Ok(Device::Ext(manager, device));
+        }
+        if let Ok(manager) = globals.bind::<ZwlrDataControlManagerV1, _, _>(qh, 1..=2, ()) {
+            let device = manager.get_data_device(seat, qh, ());
+            return Ok(Device::Wlr(manager, device));
+        }
+        Err("混成器不支持 ext-data-control 和 wlr-data-control 协议".to_string())
+    }
+
+    fn protocol(&self) -> &'static str {
+        match self {
+            Device::Ext(..) => "ext-data-control",
+            Device::Wlr(..) => "wlr-data-control",
+        }
+    }
+
+    /**
+     * 将文本设置到指定选区，wlr-data-control 第 1 版不支持 PRIMARY 时返回 false
+     */
+    fn set(&self, selection: Selection, text: String, qh: &QueueHandle<WaylandState>) -> bool {
+        match self {
+            Device::Ext(manager, device) => {
+                let source = manager.create_data_source(qh, text);
+                TEXT_MIMES
+                    .iter()
+                    .for_each(|mime| source.offer(mime.to_string()));
+                match selection {
+                    Selection::Clipboard => device.set_selection(Some(&source)),
+                    Selection::Primary => device.set_primary_selection(Some(&source)),
+                }
+            }
+            Device::Wlr(manager, device) => {
+                if selection == Selection::Primary && device.version() < 2 {
+                    return false;
+                }
+                let source = manager.create_data_source(qh, text);
+                TEXT_MIMES
+                    .iter()
+                    .for_each(|mime| source.offer(mime.to_string()));
+                match selection {
+                    Selection::Clipboard => device.set_selection(Some(&source)),
+                    Selection::Primary => device.set_primary_selection(Some(&source)),
+                }
+            }
+        }
+        true
+    }
+
+    fn destroy(&self) {
+        match self {
+            Device::Ext(_, device) => device.destroy(),
+            Device::Wlr(_, device) => device.destroy(),
+        }
+    }
+}
+
+/**
+ * 两种 data-control 协议的剪贴板内容
+ */
+enum Offer {
+    Ext(ExtDataControlOfferV1),
+    Wlr(ZwlrDataControlOfferV1),
+}
+
+impl Offer {
+    fn id(&self) -> ObjectId {
+        match self {
+            Offer::Ext(offer) => offer.id(),
+            Offer::Wlr(offer) => offer.id(),
+        }
+    }
+
+    fn receive(&self, mime: String, fd: BorrowedFd<'_>) {
+        match self {
+            Offer::Ext(offer) => offer.receive(mime, fd),
+            Offer::Wlr(offer) => offer.receive(mime, fd),
+        }
+    }
+
+    fn destroy(&self) {
+        match self {
+            Offer::Ext(offer) => offer.destroy(),
+            Offer::Wlr(offer) => offer.destroy(),
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for WaylandState {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: <WlRegistry as Proxy>::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WlSeat, ()> for WaylandState {
+    fn event(
+        _: &mut Self,
+        _: &WlSeat,
+        _: <WlSeat as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+/**
+ * 为两种 data-control 协议生成相同的事件处理
+ */
+macro_rules! data_control_dispatch {
+    ($variant:ident, $manager:ty, $manager_mod:ident, $device:ty, $device_mod:ident, $offer:ty, $offer_mod:ident, $source:ty, $source_mod:ident) => {
+        impl Dispatch<$manager, ()> for WaylandState {
+            fn event(
+                _: &mut Self,
+                _: &$manager,
+                _: $manager_mod::Event,
+                _: &(),
+                _: &Connection,
+                _: &QueueHandle<Self>,
+            ) {
+            }
+        }
+
+        impl Dispatch<$device, ()> for WaylandState {
+            fn event(
+                state: &mut Self,
+                _: &$device,
+                event: $device_mod::Event,
+                _: &(),
+                _: &Connection,
+                _: &QueueHandle<Self>,
+            ) {
+                match event {
+                    $device_mod::Event::DataOffer { id } => {
+                        state.offers.insert(id.id(), Vec::new());
+                    }
+                    $device_mod::Event::Selection { id } => {
+                        state.set_offer(Selection::Clipboard, id.map(Offer::$variant));
+                    }
+                    $device_mod::Event::PrimarySelection { id } => {
+                        state.set_offer(Selection::Primary, id.map(Offer::$variant));
+                    }
+                    $device_mod::Event::Finished => state.finished = true,
+                    _ => {}
+                }
+            }
+
+            event_created_child!(WaylandState, $device, [
+                $device_mod::EVT_DATA_OFFER_OPCODE => ($offer, ()),
+            ]);
+        }
+
+        impl Dispatch<$offer, ()> for WaylandState {
+            fn event(
+                state: &mut Self,
+                offer: &$offer,
+                event: $offer_mod::Event,
+                _: &(),
+                _: &Connection,
+                _: &QueueHandle<Self>,
+            ) {
+                if let $offer_mod::Event::Offer { mime_type } = event {
+                    state.offers.entry(offer.id()).or_default().push(mime_type);
+                }
+            }
+        }
+
+        impl Dispatch<$source, String> for WaylandState {
+            fn event(
+                _: &mut Self,
+                source: &$source,
+                event: $source_mod::Event,
+                text: &String,
+                _: &Connection,
+                _: &QueueHandle<Self>,
+            ) {
+                match event {
+                    $source_mod::Event::Send { fd, .. } => {
+                        if let Err(e) = File::from(fd).write_all(text.as_bytes()) {
+                            warn!("写入同步的选区内容失败: {}", e);
+                        }
+                    }
+                    // 选区已被其他内容替换
+                    $source_mod::Event::Cancelled => source.destroy(),
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+data_control_dispatch!(
+    Ext,
+    ExtDataControlManagerV1,
+    ext_data_control_manager_v1,
+    ExtDataControlDeviceV1,
+    ext_data_control_device_v1,
+    ExtDataControlOfferV1,
+    ext_data_control_offer_v1,
+    ExtDataControlSourceV1,
+    ext_data_control_source_v1
+);
+data_control_dispatch!(
+    Wlr,
+    ZwlrDataControlManagerV1,
+    zwlr_data_control_manager_v1,
+    ZwlrDataControlDeviceV1,
+    zwlr_data_control_device_v1,
+    ZwlrDataControlOfferV1,
+    zwlr_data_control_offer_v1,
+    ZwlrDataControlSourceV1,
+    zwlr_data_control_source_v1
+);
+
+/**
+ * 等待文件描述符可读，超时返回 false
+ */
+fn poll_readable(fd: BorrowedFd<'_>, timeout: Duration) -> bool {
+    let mut pollfd = libc::pollfd {
+        fd: fd.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let timeout = timeout.as_millis().min(i32::MAX as u128) as i32;
+    unsafe { libc::poll(&mut pollfd, 1, timeout) > 0 }
+}
+
+fn pipe() -> std::io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    unsafe { Ok((OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1]))) }
+}
+
+/**
+ * 解析 text/uri-list，只保留本地文件路径
+ */
+fn parse_uri_list(list: &str) -> Vec<String> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.strip_prefix("file://"))
+        // file://host/path 中的主机名部分，本机通常为空或 localhost
+        .filter_map(|rest| rest.find('/').map(|pos| &rest[pos..]))
+        .map(percent_decode)
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
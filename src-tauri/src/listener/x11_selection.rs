@@ -0,0 +1,337 @@
+use crate::listener::handler::ClipboardManager;
+use crate::listener::selection::{Debounce, Selection, SelectionOptions, TextReader};
+use crate::listener::state::LISTENER_STATE;
+use log::{info, warn};
+use std::collections::{HashMap, VecDeque};
+use std::os::fd::AsRawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use x11rb::connection::Connection;
+use x11rb::protocol::xfixes::{ConnectionExt as XFixesConnectionExt, SelectionEventMask};
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ConnectionExt, CreateWindowAux, EventMask, PropMode, SelectionNotifyEvent,
+    SelectionRequestEvent, Window, WindowClass, SELECTION_NOTIFY_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as WrapperConnectionExt;
+use x11rb::{COPY_DEPTH_FROM_PARENT, CURRENT_TIME, NONE};
+
+/**
+ * 等待 X11 事件时检查停止标记的间隔
+ */
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/**
+ * 读取选区内容的超时时间
+ */
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/**
+ * X11 下监听 PRIMARY 选区，并在需要时同步 PRIMARY 和 CLIPBOARD，CLIPBOARD 的记录仍由 clipboard-rs 负责
+ */
+pub struct X11SelectionWatcher {
+    conn: RustConnection,
+    window: Window,
+    primary: Atom,
+    clipboard: Atom,
+    utf8_string: Atom,
+    targets: Atom,
+    property: Atom,
+    options: SelectionOptions,
+    /**
+     * 同步时由本程序持有的选区及其文本
+     */
+    owned: HashMap<Atom, String>,
+    /**
+     * 读取选区内容时收到的其他事件，读取完成后再处理
+     */
+    deferred: VecDeque<Event>,
+    debounce: Debounce,
+    last_primary: Option<String>,
+}
+
+impl X11SelectionWatcher {
+    pub fn connect(options: SelectionOptions) -> Result<Self, String> {
+        let (conn, screen) = x11rb::connect(None).map_err(|e| e.to_string())?;
+        let root = conn.setup().roots[screen].root;
+        conn.xfixes_query_version(5, 0)
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| format!("X 服务器不支持 XFixes 扩展: {}", e))?;
+
+        let window = conn.generate_id().map_err(|e| e.to_string())?;
+        conn.create_window(
+            COPY_DEPTH_FROM_PARENT,
+            window,
+            root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_ONLY,
+            0,
+            &CreateWindowAux::new(),
+        )
+        .map_err(|e| e.to_string())?;
+
+        let mut watcher = X11SelectionWatcher {
+            primary: AtomEnum::PRIMARY.into(),
+            clipboard: intern_atom(&conn, b"CLIPBOARD")?,
+            utf8_string: intern_atom(&conn, b"UTF8_STRING")?,
+            targets: intern_atom(&conn, b"TARGETS")?,
+            property: intern_atom(&conn, b"EASYPASTE_SELECTION")?,
+            conn,
+            window,
+            options,
+            owned: HashMap::new(),
+            deferred: VecDeque::new(),
+            debounce: Debounce::default(),
+            last_primary: None,
+        };
+        watcher.select_input(watcher.primary)?;
+        if options.sync {
+            watcher.select_input(watcher.clipboard)?;
+        }
+        watcher.conn.flush().map_err(|e| e.to_string())?;
+        Ok(watcher)
+    }
+
+    fn select_input(&mut self, selection: Atom) -> Result<(), String> {
+        self.conn
+            .xfixes_select_selection_input(
+                self.window,
+                selection,
+                SelectionEventMask::SET_SELECTION_OWNER,
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /**
+     * 阻塞监听选区变化，直到 stop 或 done 被设置
+     */
+    pub fn watch(
+        &mut self,
+        manager: &ClipboardManager,
+        stop: &AtomicBool,
+        done: &AtomicBool,
+    ) -> Result<(), String> {
+        info!("开始监听 X11 PRIMARY 选区变化...");
+        while !stop.load(Ordering::SeqCst) && !done.load(Ordering::SeqCst) {
+            while let Some(event) = self.next_event()? {
+                self.handle_event(event);
+            }
+            if self.debounce.take_due() {
+                self.primary_settled(manager);
+            }
+            let timeout = self
+                .debounce
+                .remaining()
+                .map_or(POLL_INTERVAL, |remaining| remaining.min(POLL_INTERVAL));
+            poll_readable(&self.conn, timeout);
+        }
+        let _ = self.conn.destroy_window(self.window);
+        let _ = self.conn.flush();
+        Ok(())
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event>, String> {
+        if let Some(event) = self.deferred.pop_front() {
+            return Ok(Some(event));
+        }
+        self.conn
+            .poll_for_event()
+            .map_err(|e| format!("读取 X11 事件失败: {}", e))
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::XfixesSelectionNotify(e) => {
+                // 本程序同步写入的选区不需要处理
+                if e.owner == self.window {
+                    return;
+                }
+                if e.selection == self.primary {
+                    self.debounce.touch();
+                } else if e.selection == self.clipboard && self.options.sync {
+                    if let Some(text) = self.read_selection(self.clipboard) {
+                        self.own(self.primary, text);
+                    }
+                }
+            }
+            Event::SelectionRequest(e) => self.serve(e),
+            Event::SelectionClear(e) => {
+                self.owned.remove(&e.selection);
+            }
+            _ => {}
+        }
+    }
+
+    /**
+     * PRIMARY 选区停止变化后读取内容
+     */
+    fn primary_settled(&mut self, manager: &ClipboardManager) {
+        let text = match self.read_selection(self.primary) {
+            Some(text) if !text.trim().is_empty() => text,
+            _ => return,
+        };
+        if self.last_primary.as_ref() == Some(&text) {
+            return;
+        }
+        self.last_primary = Some(text.clone());
+        if self.options.sync {
+            self.own(self.clipboard, text.clone());
+        }
+        if self.options.capture_primary {
+            manager.handle_change(&TextReader(text), Selection::Primary);
+        }
+    }
+
+    /**
+     * 读取选区中的 UTF-8 文本，超时或内容过大（INCR）时返回 None
+     */
+    fn read_selection(&mut self, selection: Atom) -> Option<String> {
+        self.conn
+            .convert_selection(
+                self.window,
+                selection,
+                self.utf8_string,
+                self.property,
+                CURRENT_TIME,
+            )
+            .ok()?;
+        self.conn.flush().ok()?;
+
+        let deadline = Instant::now() + READ_TIMEOUT;
+        loop {
+            match self.conn.poll_for_event().ok()? {
+                Some(Event::SelectionNotify(e))
+                    if e.requestor == self.window && e.selection == selection =>
+                {
+                    if e.property == NONE {
+                        return None;
+                    }
+                    break;
+                }
+                Some(event) => self.deferred.push_back(event),
+                None => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        warn!("读取选区内容超时");
+                        return None;
+                    }
+                    poll_readable(&self.conn, remaining.min(POLL_INTERVAL));
+                }
+            }
+        }
+
+        let reply = self
+            .conn
+            .get_property(
+                true,
+                self.window,
+                self.property,
+                AtomEnum::ANY,
+                0,
+                u32::MAX / 4,
+            )
+            .ok()?
+            .reply()
+            .ok()?;
+        if reply.type_ != self.utf8_string && reply.type_ != u32::from(AtomEnum::STRING) {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&reply.value).into_owned())
+    }
+
+    /**
+     * 持有选区，其他程序读取时由 serve 提供文本
+     */
+    fn own(&mut self, selection: Atom, text: String) {
+        // clipboard-rs 会监听到本程序持有 CLIPBOARD，记录写入的文本以便忽略
+        if selection == self.clipboard {
+            LISTENER_STATE.lock().unwrap().synced_text = Some((text.clone(), Instant::now()));
+        }
+        if self
+            .conn
+            .set_selection_owner(self.window, selection, CURRENT_TIME)
+            .is_err()
+        {
+            return;
+        }
+        self.owned.insert(selection, text);
+        let _ = self.conn.flush();
+    }
+
+    /**
+     * 响应其他程序读取本程序持有的选区
+     */
+    fn serve(&mut self, request: SelectionRequestEvent) {
+        let property = if request.property == NONE {
+            request.target
+        } else {
+            request.property
+        };
+        let string: Atom = AtomEnum::STRING.into();
+        let served = match self.owned.get(&request.selection) {
+            Some(_) if request.target == self.targets => self
+                .conn
+                .change_property32(
+                    PropMode::REPLACE,
+                    request.requestor,
+                    property,
+                    AtomEnum::ATOM,
+                    &[self.targets, self.utf8_string, string],
+                )
+                .is_ok(),
+            Some(text) if request.target == self.utf8_string || request.target == string => self
+                .conn
+                .change_property8(
+                    PropMode::REPLACE,
+                    request.requestor,
+                    property,
+                    request.target,
+                    text.as_bytes(),
+                )
+                .is_ok(),
+            _ => false,
+        };
+
+        let notify = SelectionNotifyEvent {
+            response_type: SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: request.time,
+            requestor: request.requestor,
+            selection: request.selection,
+            target: request.target,
+            property: if served { property } else { NONE },
+        };
+        let _ = self
+            .conn
+            .send_event(false, request.requestor, EventMask::NO_EVENT, notify);
+        let _ = self.conn.flush();
+    }
+}
+
+fn intern_atom(conn: &RustConnection, name: &[u8]) -> Result<Atom, String> {
+    Ok(conn
+        .intern_atom(false, name)
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?
+        .atom)
+}
+
+/**
+ * 等待 X11 连接可读，超时返回 false
+ */
+fn poll_readable(conn: &RustConnection, timeout: Duration) -> bool {
+    let mut pollfd = libc::pollfd {
+        fd: conn.stream().as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let timeout = timeout.as_millis().min(i32::MAX as u128) as i32;
+    unsafe { libc::poll(&mut pollfd, 1, timeout) > 0 }
+}
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct TreeOption {
     pub path: String,
     pub dir: bool,
     pub date: u64,
     pub size: u64,
+    /**
+     * 条目是否加密，加密的条目只能查看名称和大小
+     */
+    #[serde(default)]
+    pub encrypted: bool,
 }
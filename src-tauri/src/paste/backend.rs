@@ -0,0 +1,48 @@
+use crate::paste::error::PasteError;
+
+/**
+ * 平台相关的焦点切换和按键发送
+ */
+pub trait PasteBackend {
+    /**
+     * 获取当前获得焦点的窗口，属于本应用的窗口返回 None
+     */
+    fn active_window(&self) -> Result<Option<u64>, PasteError>;
+
+    /**
+     * 激活指定窗口
+     */
+    fn activate_window(&self, window: u64) -> Result<(), PasteError>;
+
+    /**
+     * 向当前获得焦点的窗口发送粘贴快捷键
+     */
+    fn send_paste(&self) -> Result<(), PasteError>;
+
+    /**
+     * 向当前获得焦点的窗口发送 count 次左方向键，用于把光标移动到粘贴内容中的指定位置
+     */
+    fn move_caret_left(&self, count: usize) -> Result<(), PasteError>;
+}
+
+/**
+ * 根据当前平台选择实现
+ */
+pub fn backend() -> Result<Box<dyn PasteBackend>, PasteError> {
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var_os("DISPLAY").is_some() {
+            return Ok(Box::new(crate::paste::x11::X11Backend::connect()?));
+        }
+        Err(PasteError::Unsupported {
+            message: "没有可用的 X11 显示".to_string(),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(PasteError::Unsupported {
+            message: std::env::consts::OS.to_string(),
+        })
+    }
+}
@@ -0,0 +1,116 @@
+use crate::listener::{self, ClipboardWriteRequest};
+use crate::paste::backend::backend;
+use crate::paste::error::PasteError;
+use crate::paste::queue::{self, PasteQueueItem};
+use crate::paste::state::PASTE_STATE;
+use crate::tray;
+use log::{info, warn};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/**
+ * 切换焦点后等待目标窗口就绪的时长
+ */
+const FOCUS_DELAY: Duration = Duration::from_millis(120);
+
+/**
+ * 记录当前获得焦点的窗口，需要在显示列表窗口前调用
+ */
+#[tauri::command]
+pub fn remember_target_window() {
+    let window = match backend().and_then(|backend| backend.active_window()) {
+        Ok(window) => window,
+        Err(e) => {
+            warn!("获取当前焦点窗口失败: {}", e);
+            return;
+        }
+    };
+    // 焦点在本应用窗口上时保留之前记录的目标窗口
+    if let Some(window) = window {
+        info!("记录粘贴目标窗口: {:#x}", window);
+        PASTE_STATE.lock().unwrap().target_window = Some(window);
+    }
+}
+
+/**
+ * 写入剪贴板后隐藏列表窗口，切换回之前的窗口并发送粘贴快捷键
+ * caret_left 为粘贴后光标需要向左移动的字符数
+ */
+#[tauri::command]
+pub async fn paste_to_target(
+    app: AppHandle,
+    request: Option<ClipboardWriteRequest>,
+    caret_left: Option<usize>,
+) -> Result<(), PasteError> {
+    let target = PASTE_STATE
+        .lock()
+        .unwrap()
+        .target_window
+        .ok_or(PasteError::NoTarget)?;
+
+    if let Some(request) = request {
+        listener::write_to_clipboard(request).map_err(|error| PasteError::Clipboard { error })?;
+    }
+
+    if let Some(win) = app.get_webview_window("list") {
+        win.hide().map_err(|e| PasteError::Window {
+            message: e.to_string(),
+        })?;
+        tray::hide_win_msg();
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let backend = backend()?;
+        backend.activate_window(target)?;
+        std::thread::sleep(FOCUS_DELAY);
+        backend.send_paste()?;
+        match caret_left {
+            Some(count) if count > 0 => backend.move_caret_left(count),
+            _ => Ok(()),
+        }
+    })
+    .await
+    .map_err(|e| PasteError::Backend {
+        message: e.to_string(),
+    })?
+}
+
+/**
+ * 加入粘贴队列
+ */
+#[tauri::command]
+pub fn enqueue_paste(app: AppHandle, item: PasteQueueItem) {
+    queue::push(&app, item);
+}
+
+/**
+ * 查看粘贴队列
+ */
+#[tauri::command]
+pub fn get_paste_queue() -> Vec<PasteQueueItem> {
+    queue::items()
+}
+
+/**
+ * 按给定的id顺序重新排列粘贴队列
+ */
+#[tauri::command]
+pub fn reorder_paste_queue(app: AppHandle, ids: Vec<String>) -> Result<(), String> {
+    queue::reorder(&app, &ids)
+}
+
+/**
+ * 从粘贴队列中移除
+ */
+#[tauri::command]
+pub fn remove_from_paste_queue(app: AppHandle, id: String) {
+    queue::remove(&app, &id);
+}
+
+/**
+ * 清空粘贴队列
+ */
+#[tauri::command]
+pub fn clear_paste_queue(app: AppHandle) {
+    queue::clear(&app);
+}
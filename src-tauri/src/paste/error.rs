@@ -0,0 +1,49 @@
+use crate::listener::ClipboardWriteError;
+use serde::Serialize;
+use std::fmt;
+
+/**
+ * 粘贴到目标窗口失败的原因
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum PasteError {
+    /**
+     * 当前平台或桌面环境不支持
+     */
+    Unsupported { message: String },
+    /**
+     * 没有记录到打开列表窗口前的目标窗口
+     */
+    NoTarget,
+    /**
+     * 写入剪贴板失败
+     */
+    Clipboard { error: ClipboardWriteError },
+    /**
+     * 窗口操作失败
+     */
+    Window { message: String },
+    /**
+     * 切换焦点或发送按键失败
+     */
+    Backend { message: String },
+}
+
+impl fmt::Display for PasteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasteError::Unsupported { message } => write!(f, "不支持粘贴到目标窗口: {}", message),
+            PasteError::NoTarget => write!(f, "没有可以粘贴的目标窗口"),
+            PasteError::Clipboard { error } => write!(f, "{}", error),
+            PasteError::Window { message } => write!(f, "窗口操作失败: {}", message),
+            PasteError::Backend { message } => write!(f, "粘贴失败: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for PasteError {}
@@ -0,0 +1,11 @@
+mod backend;
+mod commands;
+mod error;
+mod queue;
+mod state;
+#[cfg(target_os = "linux")]
+mod x11;
+
+pub use commands::*;
+pub use error::PasteError;
+pub use queue::{clear as clear_queue, len as queue_len, paste_next};
@@ -0,0 +1,124 @@
+use crate::listener::{self, ClipboardWriteRequest};
+use crate::paste::backend::backend;
+use crate::paste::state::PASTE_STATE;
+use crate::tray;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/**
+ * 写入剪贴板后等待系统剪贴板更新的时长
+ */
+const WRITE_DELAY: Duration = Duration::from_millis(50);
+
+/**
+ * 粘贴队列中的一项
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PasteQueueItem {
+    /**
+     * 剪贴板记录id，用于查看和排序
+     */
+    pub id: String,
+    /**
+     * 在托盘和队列列表中显示的摘要
+     */
+    pub label: String,
+    pub request: ClipboardWriteRequest,
+}
+
+/**
+ * 获取队列中的所有项
+ */
+pub fn items() -> Vec<PasteQueueItem> {
+    PASTE_STATE.lock().unwrap().queue.iter().cloned().collect()
+}
+
+/**
+ * 队列中剩余的数量
+ */
+pub fn len() -> usize {
+    PASTE_STATE.lock().unwrap().queue.len()
+}
+
+pub fn push(app: &AppHandle, item: PasteQueueItem) {
+    PASTE_STATE.lock().unwrap().queue.push_back(item);
+    notify_changed(app);
+}
+
+pub fn remove(app: &AppHandle, id: &str) {
+    PASTE_STATE
+        .lock()
+        .unwrap()
+        .queue
+        .retain(|item| item.id != id);
+    notify_changed(app);
+}
+
+pub fn clear(app: &AppHandle) {
+    PASTE_STATE.lock().unwrap().queue.clear();
+    notify_changed(app);
+}
+
+/**
+ * 按给定的id顺序重新排列队列，id必须和队列中的项一一对应
+ */
+pub fn reorder(app: &AppHandle, ids: &[String]) -> Result<(), String> {
+    {
+        let mut state = PASTE_STATE.lock().unwrap();
+        if ids.len() != state.queue.len() {
+            return Err(String::from("排序的数量和粘贴队列不一致"));
+        }
+        let mut reordered = Vec::with_capacity(ids.len());
+        for id in ids {
+            let index = state
+                .queue
+                .iter()
+                .position(|item| &item.id == id)
+                .ok_or_else(|| format!("粘贴队列中没有: {}", id))?;
+            reordered.push(state.queue.remove(index).unwrap());
+        }
+        state.queue.extend(reordered);
+    }
+    notify_changed(app);
+    Ok(())
+}
+
+/**
+ * 取出队列中的下一项写入剪贴板，并粘贴到当前焦点窗口
+ */
+pub fn paste_next(app: &AppHandle) {
+    let item = PASTE_STATE.lock().unwrap().queue.pop_front();
+    let item = match item {
+        Some(item) => item,
+        None => {
+            info!("粘贴队列为空");
+            return;
+        }
+    };
+    notify_changed(app);
+
+    info!("粘贴队列粘贴: {}", item.label);
+    if let Err(e) = listener::write_to_clipboard(item.request) {
+        error!("粘贴队列写入剪贴板失败: {}", e);
+        return;
+    }
+    tauri::async_runtime::spawn_blocking(|| {
+        std::thread::sleep(WRITE_DELAY);
+        if let Err(e) = backend().and_then(|backend| backend.send_paste()) {
+            warn!("粘贴队列发送粘贴快捷键失败，内容已写入剪贴板: {}", e);
+        }
+    });
+}
+
+/**
+ * 队列变化后更新托盘并通知前端
+ */
+fn notify_changed(app: &AppHandle) {
+    if let Err(e) = tray::reload_tray_menu(app.clone()) {
+        error!("更新托盘粘贴队列状态失败: {}", e);
+    }
+    let _ = app.emit("paste-queue-change", items());
+}
@@ -0,0 +1,21 @@
+use crate::paste::queue::PasteQueueItem;
+use lazy_static::lazy_static;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+pub struct PasteState {
+    /**
+     * 打开列表窗口前获得焦点的窗口
+     */
+    pub target_window: Option<u64>,
+    /**
+     * 粘贴队列，每次粘贴取出第一项
+     */
+    pub queue: VecDeque<PasteQueueItem>,
+}
+
+lazy_static! {
+    pub static ref PASTE_STATE: Arc<Mutex<PasteState>> =
+        Arc::new(Mutex::new(PasteState::default()));
+}
@@ -0,0 +1,200 @@
+use crate::paste::backend::PasteBackend;
+use crate::paste::error::PasteError;
+use std::time::{Duration, Instant};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ClientMessageEvent, ConnectionExt, EventMask, KeyButMask, Keycode, Window,
+    KEY_PRESS_EVENT, KEY_RELEASE_EVENT,
+};
+use x11rb::protocol::xtest::ConnectionExt as XTestConnectionExt;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as WrapperConnectionExt;
+use x11rb::CURRENT_TIME;
+
+/**
+ * 左 Ctrl 键的 keysym
+ */
+const XK_CONTROL_L: u32 = 0xffe3;
+/**
+ * v 键的 keysym
+ */
+#[allow(non_upper_case_globals)]
+const XK_v: u32 = 0x0076;
+/**
+ * 左方向键的 keysym
+ */
+const XK_LEFT: u32 = 0xff51;
+/**
+ * 等待用户松开修饰键的最长时间
+ */
+const MODIFIER_RELEASE_TIMEOUT: Duration = Duration::from_millis(1000);
+/**
+ * 检查修饰键状态的间隔
+ */
+const MODIFIER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/**
+ * _NET_ACTIVE_WINDOW 消息的来源：由工具类程序发起，窗口管理器不会拦截
+ */
+const SOURCE_PAGER: u32 = 2;
+
+/**
+ * 基于 EWMH 切换焦点、XTest 模拟按键的 X11 实现
+ */
+pub struct X11Backend {
+    conn: RustConnection,
+    root: Window,
+    net_active_window: Atom,
+    net_wm_pid: Atom,
+}
+
+impl X11Backend {
+    pub fn connect() -> Result<Self, PasteError> {
+        let (conn, screen) = x11rb::connect(None).map_err(backend_error)?;
+        let root = conn.setup().roots[screen].root;
+        let net_active_window = intern_atom(&conn, b"_NET_ACTIVE_WINDOW")?;
+        let net_wm_pid = intern_atom(&conn, b"_NET_WM_PID")?;
+        Ok(Self {
+            conn,
+            root,
+            net_active_window,
+            net_wm_pid,
+        })
+    }
+
+    /**
+     * 读取窗口的 32 位属性
+     */
+    fn property_u32(&self, window: Window, property: Atom, kind: AtomEnum) -> Option<u32> {
+        self.conn
+            .get_property(false, window, property, kind, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?
+            .value32()?
+            .next()
+    }
+
+    /**
+     * 查找 keysym 对应的 keycode
+     */
+    fn keycode(&self, keysym: u32) -> Result<Keycode, PasteError> {
+        let setup = self.conn.setup();
+        let (min, max) = (setup.min_keycode, setup.max_keycode);
+        let mapping = self
+            .conn
+            .get_keyboard_mapping(min, max - min + 1)
+            .map_err(backend_error)?
+            .reply()
+            .map_err(backend_error)?;
+        let per_keycode = mapping.keysyms_per_keycode as usize;
+        mapping
+            .keysyms
+            .chunks(per_keycode.max(1))
+            .position(|keysyms| keysyms.contains(&keysym))
+            .map(|index| min + index as u8)
+            .ok_or_else(|| PasteError::Backend {
+                message: format!("找不到按键: {:#x}", keysym),
+            })
+    }
+
+    /**
+     * 等待用户松开 Shift、Ctrl、Alt、Super，否则模拟的 Ctrl+V 会和仍按住的快捷键修饰键组合
+     * 超时后仍然发送
+     */
+    fn wait_modifiers_released(&self) -> Result<(), PasteError> {
+        let modifiers =
+            KeyButMask::SHIFT | KeyButMask::CONTROL | KeyButMask::MOD1 | KeyButMask::MOD4;
+        let deadline = Instant::now() + MODIFIER_RELEASE_TIMEOUT;
+        loop {
+            let mask = self
+                .conn
+                .query_pointer(self.root)
+                .map_err(backend_error)?
+                .reply()
+                .map_err(backend_error)?
+                .mask;
+            if !mask.intersects(modifiers) || Instant::now() >= deadline {
+                return Ok(());
+            }
+            std::thread::sleep(MODIFIER_POLL_INTERVAL);
+        }
+    }
+
+    fn fake_key(&self, event_type: u8, keycode: Keycode) -> Result<(), PasteError> {
+        self.conn
+            .xtest_fake_input(event_type, keycode, CURRENT_TIME, self.root, 0, 0, 0)
+            .map_err(backend_error)?;
+        Ok(())
+    }
+}
+
+impl PasteBackend for X11Backend {
+    fn active_window(&self) -> Result<Option<u64>, PasteError> {
+        let window = self
+            .property_u32(self.root, self.net_active_window, AtomEnum::WINDOW)
+            .filter(|window| *window != 0);
+        let window = match window {
+            Some(window) => window,
+            None => return Ok(None),
+        };
+        // 本应用的窗口不作为粘贴目标
+        let pid = self.property_u32(window, self.net_wm_pid, AtomEnum::CARDINAL);
+        if pid == Some(std::process::id()) {
+            return Ok(None);
+        }
+        Ok(Some(window as u64))
+    }
+
+    fn activate_window(&self, window: u64) -> Result<(), PasteError> {
+        let event = ClientMessageEvent::new(
+            32,
+            window as Window,
+            self.net_active_window,
+            [SOURCE_PAGER, CURRENT_TIME, 0, 0, 0],
+        );
+        self.conn
+            .send_event(
+                false,
+                self.root,
+                EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+                event,
+            )
+            .map_err(backend_error)?;
+        self.conn.sync().map_err(backend_error)
+    }
+
+    fn send_paste(&self) -> Result<(), PasteError> {
+        let control = self.keycode(XK_CONTROL_L)?;
+        let v = self.keycode(XK_v)?;
+        self.wait_modifiers_released()?;
+        self.fake_key(KEY_PRESS_EVENT, control)?;
+        self.fake_key(KEY_PRESS_EVENT, v)?;
+        self.fake_key(KEY_RELEASE_EVENT, v)?;
+        self.fake_key(KEY_RELEASE_EVENT, control)?;
+        self.conn.sync().map_err(backend_error)
+    }
+
+    fn move_caret_left(&self, count: usize) -> Result<(), PasteError> {
+        let left = self.keycode(XK_LEFT)?;
+        for _ in 0..count {
+            self.fake_key(KEY_PRESS_EVENT, left)?;
+            self.fake_key(KEY_RELEASE_EVENT, left)?;
+        }
+        self.conn.sync().map_err(backend_error)
+    }
+}
+
+fn intern_atom(conn: &RustConnection, name: &[u8]) -> Result<Atom, PasteError> {
+    Ok(conn
+        .intern_atom(false, name)
+        .map_err(backend_error)?
+        .reply()
+        .map_err(backend_error)?
+        .atom)
+}
+
+fn backend_error<E: std::fmt::Display>(e: E) -> PasteError {
+    PasteError::Backend {
+        message: e.to_string(),
+    }
+}
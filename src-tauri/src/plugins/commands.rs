@@ -0,0 +1,189 @@
+use crate::plugins::history;
+use crate::plugins::host::load_plugin_manifest;
+use crate::plugins::installer::{self, InstallError};
+use crate::plugins::manifest::{ManifestError, PluginManifest};
+use crate::plugins::permission::{self, Permission, PluginPermissions};
+use crate::plugins::{PluginCall, PluginError, PluginHost};
+use log::info;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+/**
+ * 调用外部插件命令
+ */
+#[tauri::command]
+pub async fn invoke_external_plugin(
+    app: AppHandle,
+    plugin_id: String,
+    plugin_name: String,
+    cmd: String,
+    payload: serde_json::Value,
+    call_id: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<String, PluginError> {
+    info!(
+        "invoke_external_plugin: {:?}, {:?}, {:?}, {:?}",
+        plugin_id, plugin_name, cmd, payload
+    );
+    let host = app.state::<PluginHost>();
+    let call = PluginCall {
+        plugin_id,
+        method: cmd,
+        params: payload,
+        call_id,
+        timeout_ms,
+    };
+    let result = host.call(&app, call).await?;
+    Ok(serde_json::json!({ "result": result }).to_string())
+}
+
+/**
+ * 取消插件调用
+ */
+#[tauri::command]
+pub fn cancel_external_plugin(app: AppHandle, call_id: String) -> bool {
+    app.state::<PluginHost>().cancel(&call_id)
+}
+
+/**
+ * 停止插件进程
+ */
+#[tauri::command]
+pub fn stop_external_plugin(app: AppHandle, plugin_id: String) {
+    app.state::<PluginHost>().stop(&plugin_id);
+}
+
+/**
+ * 获取并校验插件清单
+ */
+#[tauri::command]
+pub fn get_plugin_manifest(
+    app: AppHandle,
+    plugin_id: String,
+) -> Result<PluginManifest, ManifestError> {
+    load_plugin_manifest(&app, &plugin_id).map(|(_, manifest)| manifest)
+}
+
+/**
+ * 获取插件申请的权限和用户已授权的权限
+ */
+#[tauri::command]
+pub fn get_plugin_permissions(
+    app: AppHandle,
+    plugin_id: String,
+) -> Result<PluginPermissions, ManifestError> {
+    let (_, manifest) = load_plugin_manifest(&app, &plugin_id)?;
+    let host_api_only = manifest
+        .permissions
+        .iter()
+        .filter(|p| Permission::parse(p).is_some_and(|p| p.is_host_api_only()))
+        .cloned()
+        .collect();
+    Ok(PluginPermissions {
+        requested: manifest.permissions,
+        approved: permission::load_approved(&app, &plugin_id),
+        host_api_only,
+    })
+}
+
+/**
+ * 保存用户授权的插件权限，只能授权插件清单中申请的权限
+ */
+#[tauri::command]
+pub fn approve_plugin_permissions(
+    app: AppHandle,
+    plugin_id: String,
+    permissions: Vec<String>,
+) -> Result<(), String> {
+    let (_, manifest) = load_plugin_manifest(&app, &plugin_id).map_err(|e| e.to_string())?;
+    let undeclared = permission::unapproved(&permissions, &manifest.permissions);
+    if !undeclared.is_empty() {
+        return Err(format!("插件没有申请这些权限: {}", undeclared.join(", ")));
+    }
+    info!("插件 {} 授权: {:?}", plugin_id, permissions);
+    permission::save_approved(&app, &plugin_id, &permissions)?;
+    // 重启插件进程，让新的授权生效
+    app.state::<PluginHost>().stop(&plugin_id);
+    Ok(())
+}
+
+/**
+ * 校验安装包并读取其中的插件清单，安装前用于展示插件申请的权限
+ */
+#[tauri::command]
+pub async fn inspect_plugin_archive(
+    app: AppHandle,
+    archive_path: String,
+    sha256: Option<String>,
+    signature: String,
+) -> Result<PluginManifest, InstallError> {
+    info!("inspect_plugin_archive: {:?}", archive_path);
+    installer::inspect(
+        &app,
+        Path::new(&archive_path),
+        sha256.as_deref(),
+        &signature,
+    )
+    .await
+}
+
+/**
+ * 从本地安装包安装插件，安装前校验校验和、签名、清单和语言文件
+ */
+#[tauri::command]
+pub async fn install_plugin(
+    app: AppHandle,
+    archive_path: String,
+    sha256: Option<String>,
+    signature: String,
+    approved_permissions: Vec<String>,
+) -> Result<PluginManifest, InstallError> {
+    info!("install_plugin: {:?}", archive_path);
+    installer::install(
+        &app,
+        Path::new(&archive_path),
+        sha256.as_deref(),
+        &signature,
+        &approved_permissions,
+    )
+    .await
+}
+
+/**
+ * 从本地安装包升级插件，升级失败时保留旧版本
+ */
+#[tauri::command]
+pub async fn upgrade_plugin(
+    app: AppHandle,
+    archive_path: String,
+    sha256: Option<String>,
+    signature: String,
+    approved_permissions: Vec<String>,
+) -> Result<PluginManifest, InstallError> {
+    info!("upgrade_plugin: {:?}", archive_path);
+    installer::upgrade(
+        &app,
+        Path::new(&archive_path),
+        sha256.as_deref(),
+        &signature,
+        &approved_permissions,
+    )
+    .await
+}
+
+/**
+ * 卸载插件
+ */
+#[tauri::command]
+pub async fn uninstall_plugin(app: AppHandle, plugin_id: String) -> Result<(), InstallError> {
+    info!("uninstall_plugin: {:?}", plugin_id);
+    installer::uninstall(&app, &plugin_id).await
+}
+
+/**
+ * 列表窗口返回插件请求的剪贴板历史
+ */
+#[tauri::command]
+pub fn resolve_plugin_history(request_id: u64, items: Vec<serde_json::Value>) {
+    history::resolve(request_id, items);
+}
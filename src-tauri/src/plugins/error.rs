@@ -0,0 +1,103 @@
+use crate::plugins::manifest::ManifestError;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/**
+ * 插件调用错误
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum PluginError {
+    /**
+     * 插件清单不合法
+     */
+    InvalidManifest { error: ManifestError },
+    /**
+     * 调用了插件清单中未声明的命令
+     */
+    UndeclaredCommand { command: String },
+    /**
+     * 插件申请的权限没有经过用户授权
+     */
+    PermissionNotApproved { permissions: Vec<String> },
+    /**
+     * 插件进程启动失败
+     */
+    SpawnFailed { message: String },
+    /**
+     * 插件进程未运行（正在重启或已停止）
+     */
+    NotRunning,
+    /**
+     * 调用过程中插件进程退出
+     */
+    Exited,
+    /**
+     * 与插件进程通信失败
+     */
+    Io { message: String },
+    /**
+     * 调用超时
+     */
+    Timeout { timeout_ms: u64 },
+    /**
+     * 调用被取消
+     */
+    Cancelled,
+    /**
+     * 插件单条输出超过大小限制
+     */
+    OutputTooLarge { limit: usize },
+    /**
+     * 插件返回的错误
+     */
+    Plugin {
+        code: i64,
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        data: Option<Value>,
+    },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::InvalidManifest { error } => {
+                write!(f, "Invalid plugin manifest: {}", error)
+            }
+            PluginError::UndeclaredCommand { command } => {
+                write!(f, "Plugin command is not declared: {}", command)
+            }
+            PluginError::PermissionNotApproved { permissions } => {
+                write!(
+                    f,
+                    "Plugin permissions not approved: {}",
+                    permissions.join(", ")
+                )
+            }
+            PluginError::SpawnFailed { message } => {
+                write!(f, "Failed to spawn plugin: {}", message)
+            }
+            PluginError::NotRunning => write!(f, "Plugin process is not running"),
+            PluginError::Exited => write!(f, "Plugin process exited"),
+            PluginError::Io { message } => write!(f, "Plugin io error: {}", message),
+            PluginError::Timeout { timeout_ms } => {
+                write!(f, "Plugin call timed out after {}ms", timeout_ms)
+            }
+            PluginError::Cancelled => write!(f, "Plugin call cancelled"),
+            PluginError::OutputTooLarge { limit } => {
+                write!(f, "Plugin output exceeds {} bytes", limit)
+            }
+            PluginError::Plugin { code, message, .. } => {
+                write!(f, "Plugin error (code {}): {}", code, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
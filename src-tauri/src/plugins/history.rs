@@ -0,0 +1,51 @@
+use lazy_static::lazy_static;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/**
+ * 等待前端返回剪贴板历史的最长时间
+ */
+const HISTORY_TIMEOUT: Duration = Duration::from_secs(5);
+
+lazy_static! {
+    /**
+     * 等待前端返回结果的历史请求
+     */
+    static ref REQUESTS: Mutex<HashMap<u64, mpsc::Sender<Vec<Value>>>> =
+        Mutex::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/**
+ * 读取最近的剪贴板历史，历史记录保存在前端的数据库中，由列表窗口查询后返回
+ * 会阻塞等待前端返回，只能在后台线程中调用
+ */
+pub fn read(app: &AppHandle, limit: u64) -> Result<Vec<Value>, String> {
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let (tx, rx) = mpsc::channel();
+    REQUESTS.lock().unwrap().insert(id, tx);
+
+    let payload = serde_json::json!({ "requestId": id, "limit": limit });
+    let result = match app.emit_to("list", "plugin-history-request", payload) {
+        Ok(_) => rx
+            .recv_timeout(HISTORY_TIMEOUT)
+            .map_err(|_| "读取剪贴板历史超时".to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    REQUESTS.lock().unwrap().remove(&id);
+    result
+}
+
+/**
+ * 前端返回查询到的历史记录
+ */
+pub fn resolve(request_id: u64, items: Vec<Value>) {
+    if let Some(tx) = REQUESTS.lock().unwrap().remove(&request_id) {
+        let _ = tx.send(items);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::i18n::get_plugins_path;
+use crate::plugins::error::PluginError;
+use crate::plugins::host_api::HostApi;
+use crate::plugins::manifest::{is_valid_id, ManifestError, PluginManifest, MANIFEST_FILE_NAME};
+use crate::plugins::permission::{self, Permission};
+use crate::plugins::process::{CallOptions, LaunchSpec, PluginProcess, ResourceLimits};
+use log::{info, warn};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::oneshot;
+
+/**
+ * 默认调用超时时长，单位：毫秒
+ */
+const DEFAULT_CALL_TIMEOUT_MS: u64 = 30_000;
+/**
+ * 等待插件进程退出的最长时间
+ */
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/**
+ * 一次插件调用
+ */
+pub struct PluginCall {
+    pub plugin_id: String,
+    pub method: String,
+    pub params: Value,
+    /**
+     * 调用id，由前端生成，用于取消调用
+     */
+    pub call_id: Option<String>,
+    /**
+     * 调用超时时长，单位：毫秒，为空时使用设置中的默认值
+     */
+    pub timeout_ms: Option<u64>,
+}
+
+/**
+ * 插件宿主，管理所有常驻的插件进程
+ */
+pub struct PluginHost {
+    processes: Mutex<HashMap<String, Arc<PluginProcess>>>,
+    calls: Mutex<HashMap<String, oneshot::Sender<()>>>,
+}
+
+impl PluginHost {
+    pub fn new() -> Self {
+        Self {
+            processes: Mutex::new(HashMap::new()),
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /**
+     * 调用插件命令，插件进程不存在时自动启动
+     */
+    pub async fn call(&self, app: &AppHandle, call: PluginCall) -> Result<Value, PluginError> {
+        let (plugin_dir, manifest) = load_plugin_manifest(app, &call.plugin_id)
+            .map_err(|error| PluginError::InvalidManifest { error })?;
+        if !manifest.has_command(&call.method) {
+            return Err(PluginError::UndeclaredCommand {
+                command: call.method,
+            });
+        }
+
+        let process = self.get_or_start(app, &plugin_dir, &manifest)?;
+        let timeout_ms = call.timeout_ms.unwrap_or_else(|| {
+            load_u64_setting(app, "pluginCallTimeout").unwrap_or(DEFAULT_CALL_TIMEOUT_MS)
+        });
+
+        let cancel = call.call_id.as_ref().map(|call_id| {
+            let (tx, rx) = oneshot::channel();
+            self.calls.lock().unwrap().insert(call_id.clone(), tx);
+            rx
+        });
+        let options = CallOptions {
+            timeout: Duration::from_millis(timeout_ms),
+            cancel,
+        };
+
+        let result = process.call(&call.method, call.params, options).await;
+        if let Some(call_id) = &call.call_id {
+            self.calls.lock().unwrap().remove(call_id);
+        }
+        result
+    }
+
+    /**
+     * 取消正在进行的调用
+     */
+    pub fn cancel(&self, call_id: &str) -> bool {
+        match self.calls.lock().unwrap().remove(call_id) {
+            Some(sender) => {
+                info!("取消插件调用: {}", call_id);
+                sender.send(()).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    /**
+     * 停止指定插件的进程
+     */
+    pub fn stop(&self, plugin_id: &str) {
+        if let Some(process) = self.processes.lock().unwrap().remove(plugin_id) {
+            info!("停止插件进程: {}", plugin_id);
+            process.stop();
+        }
+    }
+
+    /**
+     * 停止插件进程并等待退出，用于卸载和升级前释放插件文件
+     */
+    pub async fn stop_and_wait(&self, plugin_id: &str) {
+        let process = self.processes.lock().unwrap().remove(plugin_id);
+        if let Some(process) = process {
+            info!("停止插件进程: {}", plugin_id);
+            process.stop();
+            if !process.wait_exited(STOP_TIMEOUT).await {
+                warn!("等待插件 {} 进程退出超时", plugin_id);
+            }
+        }
+    }
+
+    /**
+     * 停止所有插件进程
+     */
+    pub fn stop_all(&self) {
+        for (plugin_id, process) in self.processes.lock().unwrap().drain() {
+            info!("停止插件进程: {}", plugin_id);
+            process.stop();
+        }
+    }
+
+    /**
+     * 获取插件进程，不存在或已经不可用时重新启动
+     */
+    fn get_or_start(
+        &self,
+        app: &AppHandle,
+        plugin_dir: &Path,
+        manifest: &PluginManifest,
+    ) -> Result<Arc<PluginProcess>, PluginError> {
+        let mut processes = self.processes.lock().unwrap();
+        if let Some(process) = processes.get(&manifest.id) {
+            if !process.is_dead() {
+                return Ok(process.clone());
+            }
+        }
+
+        // 插件申请的权限必须全部经过用户授权
+        let approved = permission::load_approved(app, &manifest.id);
+        let unapproved = permission::unapproved(&manifest.permissions, &approved);
+        if !unapproved.is_empty() {
+            return Err(PluginError::PermissionNotApproved {
+                permissions: unapproved,
+            });
+        }
+
+        let program = manifest
+            .entry_path(plugin_dir)
+            .map_err(|error| PluginError::InvalidManifest { error })?;
+        let spec = build_launch_spec(app, manifest, program);
+        let process = PluginProcess::start(manifest.id.clone(), spec)?;
+        processes.insert(manifest.id.clone(), process.clone());
+        Ok(process)
+    }
+}
+
+/**
+ * 读取并校验插件清单，返回插件目录和清单
+ */
+pub fn load_plugin_manifest(
+    app: &AppHandle,
+    plugin_id: &str,
+) -> Result<(PathBuf, PluginManifest), ManifestError> {
+    if !is_valid_id(plugin_id) {
+        return Err(ManifestError::InvalidId {
+            id: plugin_id.to_string(),
+        });
+    }
+    let plugin_dir = get_plugins_path(app.clone()).join(plugin_id);
+    // 旧版本安装的插件只有插件目录，没有清单，单独返回错误以便提示用户重新安装
+    if plugin_dir.is_dir() && !plugin_dir.join(MANIFEST_FILE_NAME).exists() {
+        return Err(ManifestError::ManifestMissing {
+            plugin_id: plugin_id.to_string(),
+        });
+    }
+    let manifest = PluginManifest::load(&plugin_dir, &app.package_info().version)?;
+    Ok((plugin_dir, manifest))
+}
+
+/**
+ * 构建插件进程的启动参数
+ */
+fn build_launch_spec(app: &AppHandle, manifest: &PluginManifest, program: PathBuf) -> LaunchSpec {
+    info!("plugin_path: {:?}", program);
+
+    let grants: Vec<Permission> = manifest
+        .permissions
+        .iter()
+        .filter_map(|p| Permission::parse(p))
+        .collect();
+    let allow_network = grants.contains(&Permission::Network);
+
+    let identifier = app.config().identifier.clone();
+    // 每个插件使用独立的数据目录
+    let plugin_data_dir = dirs::data_dir()
+        .map(|d| d.join(identifier.clone()))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("plugin-data")
+        .join(&manifest.id);
+    if let Err(e) = std::fs::create_dir_all(&plugin_data_dir) {
+        warn!("创建插件数据目录失败: {:?}, {}", plugin_data_dir, e);
+    }
+    // 日志路径
+    let log_path = match app.path().app_log_dir() {
+        Ok(log_dir) => log_dir.to_string_lossy().to_string(),
+        Err(_e) => "".to_string(),
+    };
+
+    let api = HostApi::new(app.clone(), manifest.id.clone(), grants);
+    LaunchSpec {
+        program,
+        envs: vec![
+            (
+                "EASYPASTE_DATA_DIR".to_string(),
+                plugin_data_dir.to_string_lossy().to_string(),
+            ),
+            ("EASYPASTE_IDENTIFIER".to_string(), identifier),
+            ("EASYPASTE_LOGS".to_string(), log_path),
+            (
+                "EASYPASTE_PERMISSIONS".to_string(),
+                manifest.permissions.join(","),
+            ),
+        ],
+        limits: ResourceLimits {
+            lifetime_cpu_seconds: load_u64_setting(app, "pluginLifetimeCpuSeconds"),
+            memory_bytes: load_u64_setting(app, "pluginMemoryLimit")
+                .map(|mb| mb.saturating_mul(1024 * 1024)),
+        },
+        allow_network,
+        handler: Arc::new(move |method, params| api.handle(method, params)),
+    }
+}
+
+/**
+ * 从配置文件中读取数值配置，未配置或为 0 时返回 None
+ */
+fn load_u64_setting(app: &AppHandle, key: &str) -> Option<u64> {
+    match app.store("settings.json") {
+        Ok(store) => store.get(key).and_then(|v| v.as_u64()).filter(|v| *v > 0),
+        _ => None,
+    }
+}
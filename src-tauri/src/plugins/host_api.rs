@@ -0,0 +1,188 @@
+use crate::listener::{self, ClipboardWriteRequest};
+use crate::plugins::history;
+use crate::plugins::permission::Permission;
+use crate::plugins::rpc::RpcError;
+use clipboard_rs::{Clipboard, ClipboardContext};
+use log::{info, warn};
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+
+/**
+ * 权限不足的错误码
+ */
+const PERMISSION_DENIED: i64 = -32001;
+/**
+ * 宿主执行失败的错误码
+ */
+const HOST_ERROR: i64 = -32000;
+/**
+ * 参数错误的错误码
+ */
+const INVALID_PARAMS: i64 = -32602;
+/**
+ * 方法不存在的错误码
+ */
+const METHOD_NOT_FOUND: i64 = -32601;
+/**
+ * 读取剪贴板历史默认返回的条数
+ */
+const DEFAULT_HISTORY_LIMIT: u64 = 20;
+/**
+ * 读取剪贴板历史最多返回的条数
+ */
+const MAX_HISTORY_LIMIT: u64 = 200;
+
+/**
+ * 插件可以回调的宿主接口，每个插件只能调用已授权的接口
+ */
+pub struct HostApi {
+    app: AppHandle,
+    plugin_id: String,
+    grants: Vec<Permission>,
+}
+
+impl HostApi {
+    pub fn new(app: AppHandle, plugin_id: String, grants: Vec<Permission>) -> Self {
+        Self {
+            app,
+            plugin_id,
+            grants,
+        }
+    }
+
+    /**
+     * 处理插件发起的宿主调用
+     */
+    pub fn handle(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        info!("插件 {} 调用宿主接口: {}", self.plugin_id, method);
+        match method {
+            "host.log" => {
+                let message = param_str(&params, "message")?;
+                info!("[plugin:{}] {}", self.plugin_id, message);
+                Ok(Value::Null)
+            }
+            "clipboard.readText" => {
+                self.require(&Permission::ClipboardRead, method)?;
+                let ctx = ClipboardContext::new().map_err(|e| host_error(e.to_string()))?;
+                ctx.get_text()
+                    .map(Value::String)
+                    .map_err(|e| host_error(e.to_string()))
+            }
+            "clipboard.readHistory" => {
+                self.require(&Permission::ClipboardRead, method)?;
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(DEFAULT_HISTORY_LIMIT)
+                    .min(MAX_HISTORY_LIMIT);
+                history::read(&self.app, limit)
+                    .map(Value::Array)
+                    .map_err(host_error)
+            }
+            "clipboard.writeText" => {
+                self.require(&Permission::ClipboardWrite, method)?;
+                let text = param_str(&params, "text")?;
+                let request = ClipboardWriteRequest::Text {
+                    text: text.to_string(),
+                    normalize: None,
+                };
+                listener::write_to_clipboard(request)
+                    .map(|_| Value::Null)
+                    .map_err(|e| host_error(e.to_string()))
+            }
+            "fs.readTextFile" => {
+                let path = param_path(&params)?;
+                if !self.grants.iter().any(|p| p.allows_read(&path)) {
+                    return Err(self.denied(method));
+                }
+                std::fs::read_to_string(&path)
+                    .map(Value::String)
+                    .map_err(|e| host_error(e.to_string()))
+            }
+            "fs.writeTextFile" => {
+                let path = param_path(&params)?;
+                if !self.grants.iter().any(|p| p.allows_write(&path)) {
+                    return Err(self.denied(method));
+                }
+                let content = param_str(&params, "content")?;
+                std::fs::write(&path, content)
+                    .map(|_| Value::Null)
+                    .map_err(|e| host_error(e.to_string()))
+            }
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Method not found: {}", method),
+                data: None,
+            }),
+        }
+    }
+
+    /**
+     * 检查插件是否拥有指定权限
+     */
+    fn require(&self, permission: &Permission, method: &str) -> Result<(), RpcError> {
+        if self.grants.contains(permission) {
+            Ok(())
+        } else {
+            Err(self.denied(method))
+        }
+    }
+
+    fn denied(&self, method: &str) -> RpcError {
+        warn!("插件 {} 没有权限调用宿主接口: {}", self.plugin_id, method);
+        RpcError {
+            code: PERMISSION_DENIED,
+            message: format!("Permission denied: {}", method),
+            data: None,
+        }
+    }
+}
+
+fn host_error(message: String) -> RpcError {
+    RpcError {
+        code: HOST_ERROR,
+        message,
+        data: None,
+    }
+}
+
+fn invalid_params(message: &str) -> RpcError {
+    RpcError {
+        code: INVALID_PARAMS,
+        message: message.to_string(),
+        data: None,
+    }
+}
+
+/**
+ * 获取字符串参数
+ */
+fn param_str<'a>(params: &'a Value, key: &str) -> Result<&'a str, RpcError> {
+    params
+        .get(key)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| invalid_params(&format!("缺少参数: {}", key)))
+}
+
+/**
+ * 获取路径参数，只接受不含 .. 的绝对路径，避免绕过授权范围
+ */
+fn param_path(params: &Value) -> Result<PathBuf, RpcError> {
+    let path = Path::new(param_str(params, "path")?);
+    if !path.is_absolute() || path.components().any(|c| matches!(c, Component::ParentDir)) {
+        return Err(invalid_params("路径必须是不含 .. 的绝对路径"));
+    }
+    // 解析符号链接，避免通过链接跳出授权范围
+    let resolved = match path.canonicalize() {
+        Ok(resolved) => resolved,
+        Err(_) => match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => parent
+                .canonicalize()
+                .map(|p| p.join(name))
+                .unwrap_or_else(|_| path.to_path_buf()),
+            _ => path.to_path_buf(),
+        },
+    };
+    Ok(resolved)
+}
@@ -0,0 +1,815 @@
+use crate::i18n::models::PluginLanguage;
+use crate::i18n::{get_plugins_path, read_plugin_locales, I18nState};
+use crate::plugins::manifest::{is_valid_id, ManifestError, PluginManifest, MANIFEST_FILE_NAME};
+use crate::plugins::permission;
+use crate::plugins::PluginHost;
+use base64::Engine;
+use ed25519_dalek::{Signature, VerifyingKey};
+use flate2::read::GzDecoder;
+use log::{error, info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+/**
+ * 插件安装错误
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum InstallError {
+    /**
+     * 文件读写失败
+     */
+    Io { message: String },
+    /**
+     * 不支持的安装包格式
+     */
+    UnsupportedArchive { path: String },
+    /**
+     * 安装包损坏
+     */
+    CorruptArchive { message: String },
+    /**
+     * 校验和不一致
+     */
+    ChecksumMismatch { expected: String, actual: String },
+    /**
+     * 签名格式错误
+     */
+    InvalidSignature,
+    /**
+     * 没有配置可信的签名公钥
+     */
+    NoTrustedKeys,
+    /**
+     * 签名不是由可信公钥签发的
+     */
+    UntrustedSignature,
+    /**
+     * 安装包中包含不安全的条目（路径穿越、链接等）
+     */
+    UnsafeEntry { path: String },
+    /**
+     * 插件清单不合法
+     */
+    InvalidManifest { error: ManifestError },
+    /**
+     * 插件已经安装
+     */
+    AlreadyInstalled { plugin_id: String },
+    /**
+     * 插件没有安装
+     */
+    NotInstalled { plugin_id: String },
+    /**
+     * 升级的版本号不高于已安装的版本号
+     */
+    NotNewerVersion { installed: String, version: String },
+    /**
+     * 插件申请的权限没有经过用户授权
+     */
+    PermissionNotApproved { permissions: Vec<String> },
+    /**
+     * 插件语言文件不合法
+     */
+    InvalidLocale { path: String, message: String },
+    /**
+     * 安装包文件超过大小限制，单位：字节
+     */
+    ArchiveTooLarge { limit: u64 },
+    /**
+     * 解压后的总大小超过限制，单位：字节
+     */
+    ExtractedTooLarge { limit: u64 },
+    /**
+     * 安装包中的条目数量超过限制
+     */
+    TooManyEntries { limit: usize },
+}
+
+impl fmt::Display for InstallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstallError::Io { message } => write!(f, "文件读写失败: {}", message),
+            InstallError::UnsupportedArchive { path } => write!(f, "不支持的安装包格式: {}", path),
+            InstallError::CorruptArchive { message } => write!(f, "安装包已损坏: {}", message),
+            InstallError::ChecksumMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "安装包校验和不一致，期望: {}，实际: {}",
+                    expected, actual
+                )
+            }
+            InstallError::InvalidSignature => write!(f, "安装包签名格式错误"),
+            InstallError::NoTrustedKeys => write!(f, "没有配置可信的插件签名公钥"),
+            InstallError::UntrustedSignature => write!(f, "安装包签名校验失败"),
+            InstallError::UnsafeEntry { path } => write!(f, "安装包中包含不安全的文件: {}", path),
+            InstallError::InvalidManifest { error } => write!(f, "{}", error),
+            InstallError::AlreadyInstalled { plugin_id } => write!(f, "插件已安装: {}", plugin_id),
+            InstallError::NotInstalled { plugin_id } => write!(f, "插件没有安装: {}", plugin_id),
+            InstallError::NotNewerVersion { installed, version } => {
+                write!(f, "插件版本 {} 不高于已安装的版本 {}", version, installed)
+            }
+            InstallError::PermissionNotApproved { permissions } => {
+                write!(f, "插件权限没有授权: {}", permissions.join(", "))
+            }
+            InstallError::InvalidLocale { path, message } => {
+                write!(f, "插件语言文件不合法: {}, {}", path, message)
+            }
+            InstallError::ArchiveTooLarge { limit } => {
+                write!(f, "安装包超过 {} MB", limit / 1024 / 1024)
+            }
+            InstallError::ExtractedTooLarge { limit } => {
+                write!(f, "安装包解压后超过 {} MB", limit / 1024 / 1024)
+            }
+            InstallError::TooManyEntries { limit } => {
+                write!(f, "安装包中的文件超过 {} 个", limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstallError {}
+
+impl From<io::Error> for InstallError {
+    fn from(e: io::Error) -> Self {
+        InstallError::Io {
+            message: e.to_string(),
+        }
+    }
+}
+
+/**
+ * 解压时使用的临时目录名，以 . 开头不会和插件id冲突
+ */
+const EXTRACT_DIR_NAME: &str = ".extract";
+/**
+ * 升级时备份旧版本使用的临时目录名
+ */
+const BACKUP_DIR_NAME: &str = ".backup";
+/**
+ * 安装包文件的最大大小
+ */
+const MAX_ARCHIVE_SIZE: u64 = 64 * 1024 * 1024;
+/**
+ * 解压后所有文件的最大总大小
+ */
+const MAX_EXTRACTED_SIZE: u64 = 256 * 1024 * 1024;
+/**
+ * 安装包中最多包含的条目数量
+ */
+const MAX_ENTRIES: usize = 10_000;
+
+/**
+ * 安装包格式
+ */
+enum ArchiveKind {
+    Zip,
+    TarGz,
+}
+
+/**
+ * 从本地安装包安装插件，读取、校验和解压都在后台线程中进行
+ */
+pub async fn install(
+    app: &AppHandle,
+    archive_path: &Path,
+    sha256: Option<&str>,
+    signature: &str,
+    approved_permissions: &[String],
+) -> Result<PluginManifest, InstallError> {
+    let (app, archive_path, sha256, signature, approved_permissions) = (
+        app.clone(),
+        archive_path.to_path_buf(),
+        sha256.map(str::to_string),
+        signature.to_string(),
+        approved_permissions.to_vec(),
+    );
+    run_blocking(move || {
+        let data = read_verified_archive(&app, &archive_path, sha256.as_deref(), &signature)?;
+        let (plugins_path, staging) = create_staging(&app, "install")?;
+        let result = stage_plugin(&app, &archive_path, &data, &staging, &approved_permissions)
+            .and_then(|staged| install_staged(&app, &plugins_path, staged));
+        remove_staging(&staging);
+        result
+    })
+    .await
+}
+
+/**
+ * 从本地安装包升级插件，替换失败时恢复旧版本
+ */
+pub async fn upgrade(
+    app: &AppHandle,
+    archive_path: &Path,
+    sha256: Option<&str>,
+    signature: &str,
+    approved_permissions: &[String],
+) -> Result<PluginManifest, InstallError> {
+    let (app_handle, archive_path, sha256, signature, approved_permissions) = (
+        app.clone(),
+        archive_path.to_path_buf(),
+        sha256.map(str::to_string),
+        signature.to_string(),
+        approved_permissions.to_vec(),
+    );
+    let (plugins_path, staging, staged) = run_blocking(move || {
+        let data =
+            read_verified_archive(&app_handle, &archive_path, sha256.as_deref(), &signature)?;
+        let (plugins_path, staging) = create_staging(&app_handle, "upgrade")?;
+        let staged = stage_plugin(
+            &app_handle,
+            &archive_path,
+            &data,
+            &staging,
+            &approved_permissions,
+        );
+        Ok((plugins_path, staging, staged))
+    })
+    .await?;
+    let result = match staged {
+        Ok(staged) => upgrade_staged(app, &plugins_path, &staging, staged).await,
+        Err(e) => Err(e),
+    };
+    remove_staging(&staging);
+    result
+}
+
+/**
+ * 校验并解压安装包，只读取插件清单而不安装，用于安装前向用户展示插件申请的权限
+ */
+pub async fn inspect(
+    app: &AppHandle,
+    archive_path: &Path,
+    sha256: Option<&str>,
+    signature: &str,
+) -> Result<PluginManifest, InstallError> {
+    let (app, archive_path, sha256, signature) = (
+        app.clone(),
+        archive_path.to_path_buf(),
+        sha256.map(str::to_string),
+        signature.to_string(),
+    );
+    run_blocking(move || {
+        let data = read_verified_archive(&app, &archive_path, sha256.as_deref(), &signature)?;
+        let (_, staging) = create_staging(&app, "inspect")?;
+        let result = extract(&archive_path, &data, &staging.join(EXTRACT_DIR_NAME))
+            .and_then(|root| prepare_plugin_dir(&root, &staging, &app.package_info().version))
+            .map(|(_, manifest)| manifest);
+        remove_staging(&staging);
+        result
+    })
+    .await
+}
+
+/**
+ * 卸载插件：停止插件进程，删除插件目录、授权信息和翻译
+ */
+pub async fn uninstall(app: &AppHandle, plugin_id: &str) -> Result<(), InstallError> {
+    if !is_valid_id(plugin_id) {
+        return Err(InstallError::InvalidManifest {
+            error: ManifestError::InvalidId {
+                id: plugin_id.to_string(),
+            },
+        });
+    }
+    let plugins_path = get_plugins_path(app.clone());
+    let target = plugins_path.join(plugin_id);
+    if !target.is_dir() {
+        return Err(InstallError::NotInstalled {
+            plugin_id: plugin_id.to_string(),
+        });
+    }
+
+    app.state::<PluginHost>().stop_and_wait(plugin_id).await;
+
+    // 先整体移出插件目录，移动失败时插件保持原样
+    let (_, staging) = create_staging(app, "uninstall")?;
+    if let Err(e) = fs::rename(&target, staging.join(plugin_id)) {
+        remove_staging(&staging);
+        return Err(e.into());
+    }
+    remove_staging(&staging);
+
+    if let Err(e) = permission::remove_approved(app, plugin_id) {
+        warn!("删除插件 {} 授权信息失败: {}", plugin_id, e);
+    }
+    app.state::<I18nState>().remove_plugin_locales(plugin_id);
+
+    info!("插件卸载完成: {}", plugin_id);
+    Ok(())
+}
+
+/**
+ * 解压并校验完成、等待移动到插件目录的插件
+ */
+struct StagedPlugin {
+    dir: PathBuf,
+    manifest: PluginManifest,
+    locales: Vec<PluginLanguage>,
+}
+
+/**
+ * 在后台线程中执行耗时的文件操作，避免阻塞异步运行时
+ */
+async fn run_blocking<T, F>(task: F) -> Result<T, InstallError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, InstallError> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(task)
+        .await
+        .map_err(|e| InstallError::Io {
+            message: e.to_string(),
+        })?
+}
+
+/**
+ * 读取安装包并校验校验和与签名，本地安装包没有校验和时只校验签名
+ */
+fn read_verified_archive(
+    app: &AppHandle,
+    archive_path: &Path,
+    sha256: Option<&str>,
+    signature: &str,
+) -> Result<Vec<u8>, InstallError> {
+    let data = read_archive(archive_path)?;
+    if let Some(sha256) = sha256 {
+        verify_checksum(&data, sha256)?;
+    }
+    verify_signature(&data, signature, &load_trusted_keys(app))?;
+    Ok(data)
+}
+
+/**
+ * 创建临时目录，放在插件目录下以保证重命名是原子操作
+ */
+fn create_staging(app: &AppHandle, action: &str) -> Result<(PathBuf, PathBuf), InstallError> {
+    let plugins_path = get_plugins_path(app.clone());
+    fs::create_dir_all(&plugins_path)?;
+    let staging = plugins_path.join(format!(
+        ".{}-{}",
+        action,
+        chrono::Local::now().timestamp_millis()
+    ));
+    fs::create_dir_all(&staging)?;
+    Ok((plugins_path, staging))
+}
+
+fn remove_staging(staging: &Path) {
+    if staging.exists() {
+        if let Err(e) = fs::remove_dir_all(staging) {
+            warn!("删除临时目录失败: {:?}, {}", staging, e);
+        }
+    }
+}
+
+/**
+ * 在临时目录中解压，校验清单、权限和语言文件
+ */
+fn stage_plugin(
+    app: &AppHandle,
+    archive_path: &Path,
+    data: &[u8],
+    staging: &Path,
+    approved_permissions: &[String],
+) -> Result<StagedPlugin, InstallError> {
+    let root = extract(archive_path, data, &staging.join(EXTRACT_DIR_NAME))?;
+    let (dir, manifest) = prepare_plugin_dir(&root, staging, &app.package_info().version)?;
+
+    let unapproved = permission::unapproved(&manifest.permissions, approved_permissions);
+    if !unapproved.is_empty() {
+        return Err(InstallError::PermissionNotApproved {
+            permissions: unapproved,
+        });
+    }
+
+    // 先读取全部语言文件，任何一个不合法都不安装
+    let locales = read_plugin_locales(&dir, &manifest).map_err(|(path, message)| {
+        InstallError::InvalidLocale {
+            path: path.to_string_lossy().to_string(),
+            message,
+        }
+    })?;
+
+    Ok(StagedPlugin {
+        dir,
+        manifest,
+        locales,
+    })
+}
+
+/**
+ * 把校验通过的插件移动到插件目录并注册语言
+ */
+fn install_staged(
+    app: &AppHandle,
+    plugins_path: &Path,
+    staged: StagedPlugin,
+) -> Result<PluginManifest, InstallError> {
+    let manifest = staged.manifest;
+    let target = plugins_path.join(&manifest.id);
+    if target.exists() {
+        return Err(InstallError::AlreadyInstalled {
+            plugin_id: manifest.id.clone(),
+        });
+    }
+
+    fs::rename(&staged.dir, &target)?;
+    if let Err(message) = permission::save_approved(app, &manifest.id, &manifest.permissions) {
+        let _ = fs::remove_dir_all(&target);
+        return Err(InstallError::Io { message });
+    }
+    app.state::<I18nState>()
+        .insert_plugin_locales(staged.locales);
+
+    info!("插件安装完成: {} {}", manifest.id, manifest.version);
+    Ok(manifest)
+}
+
+/**
+ * 停止旧版本插件，用新版本替换插件目录，任何一步失败都恢复旧版本
+ */
+async fn upgrade_staged(
+    app: &AppHandle,
+    plugins_path: &Path,
+    staging: &Path,
+    staged: StagedPlugin,
+) -> Result<PluginManifest, InstallError> {
+    let manifest = staged.manifest;
+    let target = plugins_path.join(&manifest.id);
+    if !target.is_dir() {
+        return Err(InstallError::NotInstalled {
+            plugin_id: manifest.id.clone(),
+        });
+    }
+    check_newer_version(&target, &manifest)?;
+
+    app.state::<PluginHost>().stop_and_wait(&manifest.id).await;
+
+    let backup = staging.join(BACKUP_DIR_NAME);
+    fs::rename(&target, &backup)?;
+    if let Err(e) = fs::rename(&staged.dir, &target) {
+        restore_backup(&backup, &target);
+        return Err(e.into());
+    }
+    if let Err(message) = permission::save_approved(app, &manifest.id, &manifest.permissions) {
+        if let Err(e) = fs::remove_dir_all(&target) {
+            error!("删除新版本插件失败: {:?}, {}", target, e);
+        }
+        restore_backup(&backup, &target);
+        return Err(InstallError::Io { message });
+    }
+    app.state::<I18nState>()
+        .replace_plugin_locales(&manifest.id, staged.locales);
+
+    info!("插件升级完成: {} {}", manifest.id, manifest.version);
+    Ok(manifest)
+}
+
+/**
+ * 新版本号必须大于已安装的版本号，已安装的清单损坏时允许升级修复
+ */
+fn check_newer_version(target: &Path, manifest: &PluginManifest) -> Result<(), InstallError> {
+    let installed = match PluginManifest::read(target) {
+        Ok(installed) => installed.version,
+        Err(e) => {
+            warn!("已安装插件的清单不可用，直接升级: {}", e);
+            return Ok(());
+        }
+    };
+    match (
+        semver::Version::parse(&installed),
+        semver::Version::parse(&manifest.version),
+    ) {
+        (Ok(old), Ok(new)) if new <= old => Err(InstallError::NotNewerVersion {
+            installed,
+            version: manifest.version.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/**
+ * 恢复旧版本插件目录
+ */
+fn restore_backup(backup: &Path, target: &Path) {
+    if let Err(e) = fs::rename(backup, target) {
+        error!("恢复旧版本插件失败: {:?} -> {:?}, {}", backup, target, e);
+    }
+}
+
+/**
+ * 从配置文件中读取可信的插件签名公钥
+ */
+fn load_trusted_keys(app: &AppHandle) -> Vec<String> {
+    match app.store("settings.json") {
+        Ok(store) => store
+            .get("pluginTrustedKeys")
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/**
+ * 读取整个安装包，超过大小限制时不读取
+ */
+pub fn read_archive(path: &Path) -> Result<Vec<u8>, InstallError> {
+    let file = File::open(path)?;
+    let too_large = InstallError::ArchiveTooLarge {
+        limit: MAX_ARCHIVE_SIZE,
+    };
+    if file.metadata()?.len() > MAX_ARCHIVE_SIZE {
+        return Err(too_large);
+    }
+    // 文件可能在读取期间变大，读取时同样限制大小
+    let mut data = Vec::new();
+    file.take(MAX_ARCHIVE_SIZE + 1).read_to_end(&mut data)?;
+    if data.len() as u64 > MAX_ARCHIVE_SIZE {
+        return Err(too_large);
+    }
+    Ok(data)
+}
+
+/**
+ * 校验安装包的 SHA-256 校验和
+ */
+pub fn verify_checksum(data: &[u8], expected: &str) -> Result<(), InstallError> {
+    let actual: String = Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(())
+    } else {
+        Err(InstallError::ChecksumMismatch {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+/**
+ * 校验安装包的 ed25519 签名，签名和公钥都是 base64 编码
+ */
+pub fn verify_signature(
+    data: &[u8],
+    signature: &str,
+    trusted_keys: &[String],
+) -> Result<(), InstallError> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let signature = engine
+        .decode(signature.trim())
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or(InstallError::InvalidSignature)?;
+
+    let keys: Vec<VerifyingKey> = trusted_keys
+        .iter()
+        .filter_map(|key| engine.decode(key.trim()).ok())
+        .filter_map(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+        .filter_map(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .collect();
+    if keys.is_empty() {
+        return Err(InstallError::NoTrustedKeys);
+    }
+
+    if keys
+        .iter()
+        .any(|key| key.verify_strict(data, &signature).is_ok())
+    {
+        Ok(())
+    } else {
+        Err(InstallError::UntrustedSignature)
+    }
+}
+
+/**
+ * 解压安装包到指定目录，返回插件根目录（plugin.json 所在目录）
+ */
+pub fn extract(archive_path: &Path, data: &[u8], dest: &Path) -> Result<PathBuf, InstallError> {
+    fs::create_dir_all(dest)?;
+    match archive_kind(archive_path)? {
+        ArchiveKind::Zip => extract_zip(data, dest)?,
+        ArchiveKind::TarGz => extract_tar_gz(data, dest)?,
+    }
+    find_plugin_root(dest)
+}
+
+/**
+ * 读取解压后的插件清单，并把插件目录重命名为插件id，以便校验目录名
+ */
+pub fn prepare_plugin_dir(
+    root: &Path,
+    staging: &Path,
+    app_version: &semver::Version,
+) -> Result<(PathBuf, PluginManifest), InstallError> {
+    let manifest =
+        PluginManifest::read(root).map_err(|error| InstallError::InvalidManifest { error })?;
+    if !is_valid_id(&manifest.id) {
+        return Err(InstallError::InvalidManifest {
+            error: ManifestError::InvalidId {
+                id: manifest.id.clone(),
+            },
+        });
+    }
+
+    let plugin_dir = staging.join(&manifest.id);
+    fs::rename(root, &plugin_dir)?;
+    manifest
+        .validate(&plugin_dir, app_version)
+        .map_err(|error| InstallError::InvalidManifest { error })?;
+    Ok((plugin_dir, manifest))
+}
+
+/**
+ * 根据文件名判断安装包格式
+ */
+fn archive_kind(path: &Path) -> Result<ArchiveKind, InstallError> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if name.ends_with(".zip") {
+        Ok(ArchiveKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Ok(ArchiveKind::TarGz)
+    } else {
+        Err(InstallError::UnsupportedArchive {
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+}
+
+/**
+ * 校验条目路径，只允许普通的相对路径
+ */
+fn safe_entry_path(dest: &Path, name: &str) -> Result<PathBuf, InstallError> {
+    let path = Path::new(name);
+    let is_safe = !name.is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !is_safe {
+        return Err(InstallError::UnsafeEntry {
+            path: name.to_string(),
+        });
+    }
+    Ok(dest.join(path))
+}
+
+/**
+ * 解压时已使用的条目数量和大小，防止解压炸弹
+ */
+#[derive(Default)]
+struct ExtractBudget {
+    entries: usize,
+    bytes: u64,
+}
+
+impl ExtractBudget {
+    /**
+     * 解压下一个条目前计数
+     */
+    fn add_entry(&mut self) -> Result<(), InstallError> {
+        self.entries += 1;
+        if self.entries > MAX_ENTRIES {
+            return Err(InstallError::TooManyEntries { limit: MAX_ENTRIES });
+        }
+        Ok(())
+    }
+
+    /**
+     * 复制条目内容，按实际写入的大小计数，不信任条目头中声明的大小
+     */
+    fn copy(&mut self, reader: impl Read, file: &mut File) -> Result<(), InstallError> {
+        let remaining = MAX_EXTRACTED_SIZE - self.bytes;
+        let written = io::copy(&mut reader.take(remaining + 1), file)?;
+        if written > remaining {
+            return Err(InstallError::ExtractedTooLarge {
+                limit: MAX_EXTRACTED_SIZE,
+            });
+        }
+        self.bytes += written;
+        Ok(())
+    }
+}
+
+/**
+ * 解压 zip 安装包
+ */
+fn extract_zip(data: &[u8], dest: &Path) -> Result<(), InstallError> {
+    let corrupt = |e: zip::result::ZipError| InstallError::CorruptArchive {
+        message: e.to_string(),
+    };
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).map_err(corrupt)?;
+    // 中央目录已经给出条目数量，超过限制时不解压任何条目
+    if archive.len() > MAX_ENTRIES {
+        return Err(InstallError::TooManyEntries { limit: MAX_ENTRIES });
+    }
+    let mut budget = ExtractBudget::default();
+    for index in 0..archive.len() {
+        budget.add_entry()?;
+        let mut entry = archive.by_index(index).map_err(corrupt)?;
+        let name = entry.name().to_string();
+        if entry.is_symlink() {
+            return Err(InstallError::UnsafeEntry { path: name });
+        }
+        let target = safe_entry_path(dest, &name)?;
+        if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(&target)?;
+        budget.copy(&mut entry, &mut file)?;
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            set_mode(&target, mode)?;
+        }
+    }
+    Ok(())
+}
+
+/**
+ * 解压 tar.gz 安装包
+ */
+fn extract_tar_gz(data: &[u8], dest: &Path) -> Result<(), InstallError> {
+    let mut archive = tar::Archive::new(GzDecoder::new(Cursor::new(data)));
+    let mut budget = ExtractBudget::default();
+    let entries = archive
+        .entries()
+        .map_err(|e| InstallError::CorruptArchive {
+            message: e.to_string(),
+        })?;
+    for entry in entries {
+        budget.add_entry()?;
+        let mut entry = entry.map_err(|e| InstallError::CorruptArchive {
+            message: e.to_string(),
+        })?;
+        let name = String::from_utf8_lossy(&entry.path_bytes()).to_string();
+        let entry_type = entry.header().entry_type();
+        let target = safe_entry_path(dest, &name)?;
+        if entry_type.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if !entry_type.is_file() {
+            return Err(InstallError::UnsafeEntry { path: name });
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(&target)?;
+        budget.copy(&mut entry, &mut file)?;
+        #[cfg(unix)]
+        if let Ok(mode) = entry.header().mode() {
+            set_mode(&target, mode)?;
+        }
+    }
+    Ok(())
+}
+
+/**
+ * 保留可执行权限，去掉 setuid 等特殊权限位
+ */
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o755))
+}
+
+/**
+ * 查找插件根目录：plugin.json 在解压目录下，或者在唯一的子目录下
+ */
+fn find_plugin_root(dest: &Path) -> Result<PathBuf, InstallError> {
+    if dest.join(MANIFEST_FILE_NAME).is_file() {
+        return Ok(dest.to_path_buf());
+    }
+    let entries: Vec<PathBuf> = fs::read_dir(dest)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .collect();
+    if let [single] = entries.as_slice() {
+        if single.is_dir() && single.join(MANIFEST_FILE_NAME).is_file() {
+            return Ok(single.clone());
+        }
+    }
+    Err(InstallError::InvalidManifest {
+        error: ManifestError::NotFound {
+            path: MANIFEST_FILE_NAME.to_string(),
+        },
+    })
+}
@@ -0,0 +1,389 @@
+use crate::plugins::permission::Permission;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::path::{Component, Path, PathBuf};
+
+/**
+ * 插件清单文件名
+ */
+pub const MANIFEST_FILE_NAME: &str = "plugin.json";
+
+/**
+ * 宿主支持的插件通信协议版本：插件进程常驻，通过标准输入输出按行收发 JSON-RPC 2.0 消息
+ * 旧版本每次调用启动一次进程的插件没有 plugin.json，会被识别为 ManifestMissing
+ */
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/**
+ * 插件清单
+ */
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub version: String,
+    /**
+     * 插件使用的通信协议版本，不填时为 1
+     */
+    #[serde(default = "default_protocol")]
+    pub protocol: u32,
+    pub entry: PluginEntry,
+    pub commands: Vec<String>,
+    /**
+     * 申请的权限，只有 network 在 Linux 上由进程沙箱强制限制
+     * clipboard:read、clipboard:write、fs:read:<路径>、fs:write:<路径> 只限制对应的宿主 API，
+     * 插件进程本身以当前用户的身份运行，仍然可以直接访问用户能访问的文件
+     */
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    #[serde(default)]
+    pub locales: Vec<String>,
+    #[serde(default)]
+    pub min_app_version: Option<String>,
+}
+
+fn default_protocol() -> u32 {
+    PROTOCOL_VERSION
+}
+
+/**
+ * 各平台的插件入口（相对插件目录的路径）
+ */
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PluginEntry {
+    #[serde(default)]
+    pub windows: Option<String>,
+    #[serde(default)]
+    pub macos: Option<String>,
+    #[serde(default)]
+    pub linux: Option<String>,
+}
+
+impl PluginEntry {
+    /**
+     * 获取当前平台的入口
+     */
+    pub fn for_current_platform(&self) -> Option<&String> {
+        match std::env::consts::OS {
+            "windows" => self.windows.as_ref(),
+            "macos" => self.macos.as_ref(),
+            "linux" => self.linux.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * 插件清单校验错误
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ManifestError {
+    /**
+     * 清单文件不存在
+     */
+    NotFound { path: String },
+    /**
+     * 插件已安装但没有清单，旧版本安装的插件需要重新安装
+     */
+    ManifestMissing { plugin_id: String },
+    /**
+     * 清单文件读取失败
+     */
+    Unreadable { message: String },
+    /**
+     * 清单文件格式错误
+     */
+    Malformed { message: String },
+    /**
+     * 插件id不合法
+     */
+    InvalidId { id: String },
+    /**
+     * 插件id与插件目录名不一致
+     */
+    IdMismatch { expected: String, actual: String },
+    /**
+     * 版本号不合法
+     */
+    InvalidVersion { field: String, value: String },
+    /**
+     * 缺少当前平台的入口
+     */
+    MissingEntry { platform: String },
+    /**
+     * 路径不合法（绝对路径或跳出插件目录）
+     */
+    InvalidPath { path: String },
+    /**
+     * 清单中声明的文件不存在
+     */
+    FileMissing { path: String },
+    /**
+     * 没有声明任何命令
+     */
+    NoCommands,
+    /**
+     * 命令名不合法或重复
+     */
+    InvalidCommand { command: String },
+    /**
+     * 未知的权限
+     */
+    UnknownPermission { permission: String },
+    /**
+     * 权限重复声明
+     */
+    DuplicatePermission { permission: String },
+    /**
+     * 当前应用版本低于插件要求的最低版本
+     */
+    IncompatibleAppVersion { required: String, current: String },
+    /**
+     * 不支持的插件通信协议版本
+     */
+    UnsupportedProtocol { version: u32, supported: u32 },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::NotFound { path } => write!(f, "插件清单不存在: {}", path),
+            ManifestError::ManifestMissing { plugin_id } => {
+                write!(
+                    f,
+                    "插件 {} 缺少 {}，需要重新安装",
+                    plugin_id, MANIFEST_FILE_NAME
+                )
+            }
+            ManifestError::Unreadable { message } => write!(f, "插件清单读取失败: {}", message),
+            ManifestError::Malformed { message } => write!(f, "插件清单格式错误: {}", message),
+            ManifestError::InvalidId { id } => write!(f, "插件id不合法: {}", id),
+            ManifestError::IdMismatch { expected, actual } => {
+                write!(f, "插件id {} 与插件目录 {} 不一致", actual, expected)
+            }
+            ManifestError::InvalidVersion { field, value } => {
+                write!(f, "{} 版本号不合法: {}", field, value)
+            }
+            ManifestError::MissingEntry { platform } => {
+                write!(f, "缺少 {} 平台的插件入口", platform)
+            }
+            ManifestError::InvalidPath { path } => write!(f, "插件路径不合法: {}", path),
+            ManifestError::FileMissing { path } => write!(f, "插件文件不存在: {}", path),
+            ManifestError::NoCommands => write!(f, "插件没有声明任何命令"),
+            ManifestError::InvalidCommand { command } => write!(f, "插件命令不合法: {}", command),
+            ManifestError::UnknownPermission { permission } => {
+                write!(f, "未知的插件权限: {}", permission)
+            }
+            ManifestError::DuplicatePermission { permission } => {
+                write!(f, "插件权限重复声明: {}", permission)
+            }
+            ManifestError::IncompatibleAppVersion { required, current } => {
+                write!(
+                    f,
+                    "插件要求应用版本不低于 {}，当前版本 {}",
+                    required, current
+                )
+            }
+            ManifestError::UnsupportedProtocol { version, supported } => {
+                write!(
+                    f,
+                    "不支持插件通信协议版本 {}，当前支持的版本 {}",
+                    version, supported
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl PluginManifest {
+    /**
+     * 读取并校验插件目录中的清单
+     */
+    pub fn load(
+        plugin_dir: &Path,
+        app_version: &semver::Version,
+    ) -> Result<PluginManifest, ManifestError> {
+        let manifest = Self::read(plugin_dir)?;
+        manifest.validate(plugin_dir, app_version)?;
+        Ok(manifest)
+    }
+
+    /**
+     * 读取插件目录中的清单，不做校验
+     */
+    pub fn read(plugin_dir: &Path) -> Result<PluginManifest, ManifestError> {
+        let path = plugin_dir.join(MANIFEST_FILE_NAME);
+        if !path.is_file() {
+            return Err(ManifestError::NotFound {
+                path: path.to_string_lossy().to_string(),
+            });
+        }
+        let file = File::open(&path).map_err(|e| ManifestError::Unreadable {
+            message: e.to_string(),
+        })?;
+        serde_json::from_reader(file).map_err(|e| ManifestError::Malformed {
+            message: e.to_string(),
+        })
+    }
+
+    /**
+     * 校验清单内容
+     */
+    pub fn validate(
+        &self,
+        plugin_dir: &Path,
+        app_version: &semver::Version,
+    ) -> Result<(), ManifestError> {
+        if !is_valid_id(&self.id) {
+            return Err(ManifestError::InvalidId {
+                id: self.id.clone(),
+            });
+        }
+        if let Some(dir_name) = plugin_dir.file_name() {
+            let dir_name = dir_name.to_string_lossy();
+            if dir_name != self.id {
+                return Err(ManifestError::IdMismatch {
+                    expected: dir_name.to_string(),
+                    actual: self.id.clone(),
+                });
+            }
+        }
+
+        semver::Version::parse(&self.version).map_err(|_| ManifestError::InvalidVersion {
+            field: "version".to_string(),
+            value: self.version.clone(),
+        })?;
+
+        if self.protocol != PROTOCOL_VERSION {
+            return Err(ManifestError::UnsupportedProtocol {
+                version: self.protocol,
+                supported: PROTOCOL_VERSION,
+            });
+        }
+
+        self.entry_path(plugin_dir)?;
+
+        if self.commands.is_empty() {
+            return Err(ManifestError::NoCommands);
+        }
+        let mut commands = HashSet::new();
+        for command in &self.commands {
+            if command.trim().is_empty() || command.starts_with("$/") || !commands.insert(command) {
+                return Err(ManifestError::InvalidCommand {
+                    command: command.clone(),
+                });
+            }
+        }
+
+        let mut permissions = HashSet::new();
+        for permission in &self.permissions {
+            if Permission::parse(permission).is_none() {
+                return Err(ManifestError::UnknownPermission {
+                    permission: permission.clone(),
+                });
+            }
+            if !permissions.insert(permission) {
+                return Err(ManifestError::DuplicatePermission {
+                    permission: permission.clone(),
+                });
+            }
+        }
+
+        for locale in &self.locales {
+            resolve_file(plugin_dir, locale)?;
+        }
+
+        if let Some(min_app_version) = &self.min_app_version {
+            let required = semver::Version::parse(min_app_version).map_err(|_| {
+                ManifestError::InvalidVersion {
+                    field: "minAppVersion".to_string(),
+                    value: min_app_version.clone(),
+                }
+            })?;
+            if app_version < &required {
+                return Err(ManifestError::IncompatibleAppVersion {
+                    required: min_app_version.clone(),
+                    current: app_version.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * 获取当前平台入口文件的完整路径
+     */
+    pub fn entry_path(&self, plugin_dir: &Path) -> Result<PathBuf, ManifestError> {
+        let entry =
+            self.entry
+                .for_current_platform()
+                .ok_or_else(|| ManifestError::MissingEntry {
+                    platform: std::env::consts::OS.to_string(),
+                })?;
+        resolve_file(plugin_dir, entry)
+    }
+
+    /**
+     * 获取语言文件的完整路径
+     */
+    pub fn locale_paths(&self, plugin_dir: &Path) -> Vec<PathBuf> {
+        self.locales
+            .iter()
+            .filter_map(|locale| resolve_file(plugin_dir, locale).ok())
+            .collect()
+    }
+
+    /**
+     * 是否声明了指定命令
+     */
+    pub fn has_command(&self, command: &str) -> bool {
+        self.commands.iter().any(|c| c == command)
+    }
+}
+
+/**
+ * 插件id只能包含字母、数字、下划线、中划线和点，且不能以点开头
+ */
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+/**
+ * 解析清单中的相对路径，确保文件存在且在插件目录内
+ */
+fn resolve_file(plugin_dir: &Path, relative: &str) -> Result<PathBuf, ManifestError> {
+    let relative_path = Path::new(relative);
+    let is_safe = !relative.is_empty()
+        && relative_path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !is_safe {
+        return Err(ManifestError::InvalidPath {
+            path: relative.to_string(),
+        });
+    }
+
+    let path = plugin_dir.join(relative_path);
+    if !path.is_file() {
+        return Err(ManifestError::FileMissing {
+            path: relative.to_string(),
+        });
+    }
+    Ok(path)
+}
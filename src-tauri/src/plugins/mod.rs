@@ -0,0 +1,16 @@
+mod commands;
+mod error;
+mod history;
+mod host;
+mod host_api;
+mod installer;
+mod manifest;
+mod permission;
+mod process;
+mod rpc;
+
+pub use commands::*;
+pub use error::PluginError;
+pub use host::{load_plugin_manifest, PluginCall, PluginHost};
+pub use manifest::{ManifestError, PluginManifest};
+pub use permission::load_approved;
@@ -0,0 +1,194 @@
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+/**
+ * 保存插件授权信息的存储文件
+ */
+const PLUGINS_STORE: &str = "plugins.json";
+
+/**
+ * 插件权限
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /**
+     * 读取剪贴板内容和历史
+     */
+    ClipboardRead,
+    /**
+     * 写入剪贴板
+     */
+    ClipboardWrite,
+    /**
+     * 访问网络
+     */
+    Network,
+    /**
+     * 通过 fs.readTextFile 读取指定路径下的文件
+     * 只限制宿主 API，插件进程本身仍然以当前用户的身份运行，可以直接访问用户能访问的文件
+     */
+    FsRead(PathBuf),
+    /**
+     * 通过 fs.writeTextFile 写入指定路径下的文件，和 FsRead 一样只限制宿主 API
+     */
+    FsWrite(PathBuf),
+}
+
+impl Permission {
+    /**
+     * 解析权限字符串，格式：clipboard:read、clipboard:write、network、fs:read:<路径>、fs:write:<路径>
+     */
+    pub fn parse(value: &str) -> Option<Permission> {
+        match value {
+            "clipboard:read" => Some(Permission::ClipboardRead),
+            "clipboard:write" => Some(Permission::ClipboardWrite),
+            "network" => Some(Permission::Network),
+            _ => {
+                if let Some(path) = value.strip_prefix("fs:read:") {
+                    parse_scope(path).map(Permission::FsRead)
+                } else if let Some(path) = value.strip_prefix("fs:write:") {
+                    parse_scope(path).map(Permission::FsWrite)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /**
+     * 是否只限制宿主 API 的调用，而不限制插件进程本身
+     * 只有 Linux 上没有网络权限的插件会被放入独立的网络命名空间，其他权限都只限制宿主 API
+     */
+    pub fn is_host_api_only(&self) -> bool {
+        match self {
+            Permission::Network => !cfg!(target_os = "linux"),
+            _ => true,
+        }
+    }
+
+    /**
+     * 是否允许读取指定路径
+     */
+    pub fn allows_read(&self, path: &Path) -> bool {
+        match self {
+            Permission::FsRead(scope) | Permission::FsWrite(scope) => within_scope(path, scope),
+            _ => false,
+        }
+    }
+
+    /**
+     * 是否允许写入指定路径
+     */
+    pub fn allows_write(&self, path: &Path) -> bool {
+        match self {
+            Permission::FsWrite(scope) => within_scope(path, scope),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Permission::ClipboardRead => write!(f, "clipboard:read"),
+            Permission::ClipboardWrite => write!(f, "clipboard:write"),
+            Permission::Network => write!(f, "network"),
+            Permission::FsRead(path) => write!(f, "fs:read:{}", path.display()),
+            Permission::FsWrite(path) => write!(f, "fs:write:{}", path.display()),
+        }
+    }
+}
+
+/**
+ * 插件的权限申请和授权情况
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPermissions {
+    pub requested: Vec<String>,
+    pub approved: Vec<String>,
+    /**
+     * 申请的权限中只限制宿主 API 的部分，插件进程仍可以直接访问用户的文件和剪贴板，
+     * 授权时需要向用户说明这些权限不是对插件进程的隔离
+     */
+    pub host_api_only: Vec<String>,
+}
+
+/**
+ * 路径是否在授权范围内
+ */
+fn within_scope(path: &Path, scope: &Path) -> bool {
+    let scope = scope.canonicalize().unwrap_or_else(|_| scope.to_path_buf());
+    path.starts_with(scope)
+}
+
+/**
+ * 文件权限的路径只能是绝对路径，支持 ~ 开头表示用户目录
+ */
+fn parse_scope(path: &str) -> Option<PathBuf> {
+    let path = match path.strip_prefix("~") {
+        Some(rest) => dirs::home_dir()?.join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(path),
+    };
+    if path.is_absolute() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/**
+ * 读取用户已授权的插件权限
+ */
+pub fn load_approved(app: &AppHandle, plugin_id: &str) -> Vec<String> {
+    match app.store(PLUGINS_STORE) {
+        Ok(store) => store
+            .get(plugin_id)
+            .and_then(|v| v.get("permissions").cloned())
+            .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/**
+ * 保存用户授权的插件权限
+ */
+pub fn save_approved(
+    app: &AppHandle,
+    plugin_id: &str,
+    permissions: &[String],
+) -> Result<(), String> {
+    let store = app.store(PLUGINS_STORE).map_err(|e| e.to_string())?;
+    let mut value = store
+        .get(plugin_id)
+        .unwrap_or_else(|| serde_json::json!({}));
+    value["permissions"] = serde_json::json!(permissions);
+    store.set(plugin_id, value);
+    store.save().map_err(|e| e.to_string())
+}
+
+/**
+ * 删除插件的授权信息
+ */
+pub fn remove_approved(app: &AppHandle, plugin_id: &str) -> Result<(), String> {
+    let store = app.store(PLUGINS_STORE).map_err(|e| e.to_string())?;
+    if store.delete(plugin_id) {
+        store.save().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/**
+ * 找出插件申请了但用户没有授权的权限
+ */
+pub fn unapproved(requested: &[String], approved: &[String]) -> Vec<String> {
+    requested
+        .iter()
+        .filter(|p| !approved.contains(p))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,705 @@
+use crate::plugins::error::PluginError;
+use crate::plugins::rpc;
+use crate::plugins::rpc::RpcError;
+use log::{error, info, warn};
+use serde_json::Value;
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{oneshot, Mutex as AsyncMutex, Notify};
+
+/**
+ * 进程稳定运行超过该时长后，重置崩溃计数
+ */
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+/**
+ * 连续崩溃的最大重启次数
+ */
+const MAX_RESTARTS: u32 = 5;
+/**
+ * 重启退避的初始时长
+ */
+const BACKOFF_BASE: Duration = Duration::from_millis(500);
+/**
+ * 重启退避的最大时长
+ */
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+/**
+ * 插件进程可以继承的环境变量
+ */
+const INHERITED_ENVS: [&str; 12] = [
+    "PATH",
+    "HOME",
+    "USERPROFILE",
+    "SYSTEMROOT",
+    "TEMP",
+    "TMP",
+    "TMPDIR",
+    "LANG",
+    "LC_ALL",
+    "DISPLAY",
+    "WAYLAND_DISPLAY",
+    "XDG_RUNTIME_DIR",
+];
+/**
+ * 插件单条输出的最大字节数
+ */
+pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+type PendingMap = Mutex<HashMap<u64, oneshot::Sender<Result<Value, PluginError>>>>;
+
+/**
+ * 处理插件发起的宿主调用
+ */
+pub type HostHandler = Arc<dyn Fn(&str, Value) -> Result<Value, RpcError> + Send + Sync>;
+
+/**
+ * 插件进程资源限制（仅 Linux 生效）
+ */
+#[derive(Clone, Default)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct ResourceLimits {
+    /**
+     * 插件进程整个生命周期累计的 CPU 时间上限，单位：秒
+     * 插件进程常驻并在多次调用间复用，这是所有调用共用的总预算，不是单次调用的上限，
+     * 用完后进程被系统结束，下次调用时重新启动并获得新的预算
+     */
+    pub lifetime_cpu_seconds: Option<u64>,
+    /**
+     * 内存（虚拟地址空间）上限，单位：字节
+     */
+    pub memory_bytes: Option<u64>,
+}
+
+/**
+ * 插件进程启动参数
+ */
+#[derive(Clone)]
+pub struct LaunchSpec {
+    pub program: PathBuf,
+    pub envs: Vec<(String, String)>,
+    pub limits: ResourceLimits,
+    /**
+     * 是否允许访问网络，不允许时在 Linux 上放入独立的网络命名空间
+     */
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub allow_network: bool,
+    pub handler: HostHandler,
+}
+
+/**
+ * 单次调用的参数
+ */
+pub struct CallOptions {
+    pub timeout: Duration,
+    pub cancel: Option<oneshot::Receiver<()>>,
+}
+
+/**
+ * 读取一行输出的结果
+ */
+enum LineRead {
+    Eof,
+    Line,
+    TooLarge,
+}
+
+/**
+ * 常驻的插件进程，通过标准输入输出进行 JSON-RPC 通信
+ */
+pub struct PluginProcess {
+    plugin_id: String,
+    spec: LaunchSpec,
+    stdin: AsyncMutex<Option<ChildStdin>>,
+    pending: PendingMap,
+    next_id: AtomicU64,
+    stopped: AtomicBool,
+    crashed: AtomicBool,
+    shutdown: Notify,
+    /**
+     * 监控任务结束后置位，此时进程已经退出且不会再重启
+     */
+    exited: AtomicBool,
+    exited_notify: Notify,
+}
+
+impl PluginProcess {
+    /**
+     * 启动插件进程，并在后台监控进程状态
+     */
+    pub fn start(plugin_id: String, spec: LaunchSpec) -> Result<Arc<Self>, PluginError> {
+        let mut child = spawn_child(&plugin_id, &spec)?;
+        let process = Arc::new(PluginProcess {
+            stdin: AsyncMutex::new(child.stdin.take()),
+            plugin_id,
+            spec,
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            stopped: AtomicBool::new(false),
+            crashed: AtomicBool::new(false),
+            shutdown: Notify::new(),
+            exited: AtomicBool::new(false),
+            exited_notify: Notify::new(),
+        });
+        tauri::async_runtime::spawn(process.clone().supervise(child));
+        Ok(process)
+    }
+
+    /**
+     * 调用插件命令，支持多个调用并发进行
+     */
+    pub async fn call(
+        &self,
+        method: &str,
+        params: Value,
+        options: CallOptions,
+    ) -> Result<Value, PluginError> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+
+        let line = rpc::encode_request(id, method, &params);
+        if let Err(e) = self.write_line(&line).await {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(e);
+        }
+
+        let cancelled = async {
+            match options.cancel {
+                Some(cancel) => {
+                    if cancel.await.is_err() {
+                        std::future::pending::<()>().await;
+                    }
+                }
+                None => std::future::pending::<()>().await,
+            }
+        };
+
+        let result = tokio::select! {
+            result = rx => result.unwrap_or(Err(PluginError::Exited)),
+            _ = tokio::time::sleep(options.timeout) => Err(PluginError::Timeout {
+                timeout_ms: options.timeout.as_millis() as u64,
+            }),
+            _ = cancelled => Err(PluginError::Cancelled),
+        };
+
+        if let Err(PluginError::Timeout { .. } | PluginError::Cancelled) = &result {
+            warn!("插件 {} 调用 {} 已放弃: {:?}", self.plugin_id, method, result);
+            self.abandon(id).await;
+        }
+        result
+    }
+
+    /**
+     * 停止插件进程，不再自动重启
+     */
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.shutdown.notify_one();
+    }
+
+    /**
+     * 等待进程退出，超时返回 false
+     */
+    pub async fn wait_exited(&self, timeout: Duration) -> bool {
+        let notified = self.exited_notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.exited.load(Ordering::SeqCst) {
+            return true;
+        }
+        tokio::time::timeout(timeout, notified).await.is_ok()
+    }
+
+    /**
+     * 进程是否已经不可用（已停止或多次崩溃后放弃重启）
+     */
+    pub fn is_dead(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst) || self.crashed.load(Ordering::SeqCst)
+    }
+
+    /**
+     * 放弃等待某个调用，并通知插件取消该请求
+     */
+    async fn abandon(&self, id: u64) {
+        self.pending.lock().unwrap().remove(&id);
+        let line = rpc::encode_notification("$/cancelRequest", serde_json::json!({ "id": id }));
+        let _ = self.write_line(&line).await;
+    }
+
+    /**
+     * 监控插件进程，结束后通知等待退出的调用方
+     */
+    async fn supervise(self: Arc<Self>, child: Child) {
+        self.supervise_child(child).await;
+        self.exited.store(true, Ordering::SeqCst);
+        self.exited_notify.notify_waiters();
+    }
+
+    /**
+     * 进程异常退出后按退避时间重启，直到被停止或多次崩溃
+     */
+    async fn supervise_child(self: &Arc<Self>, child: Child) {
+        let mut child = Some(child);
+        let mut restarts: u32 = 0;
+
+        loop {
+            if let Some(running) = child.take() {
+                let started_at = Instant::now();
+                self.run_child(running).await;
+                if started_at.elapsed() >= STABLE_UPTIME {
+                    restarts = 0;
+                }
+            }
+            self.fail_pending(PluginError::Exited);
+
+            if self.stopped.load(Ordering::SeqCst) {
+                info!("插件进程已停止: {}", self.plugin_id);
+                return;
+            }
+
+            restarts += 1;
+            if restarts > MAX_RESTARTS {
+                error!(
+                    "插件 {} 连续崩溃 {} 次，不再自动重启",
+                    self.plugin_id, MAX_RESTARTS
+                );
+                self.crashed.store(true, Ordering::SeqCst);
+                return;
+            }
+
+            let delay = backoff_delay(restarts);
+            warn!(
+                "插件 {} 进程异常退出，{:?} 后进行第 {} 次重启",
+                self.plugin_id, delay, restarts
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = self.shutdown.notified() => {
+                    info!("插件进程已停止: {}", self.plugin_id);
+                    return;
+                }
+            }
+
+            match spawn_child(&self.plugin_id, &self.spec) {
+                Ok(new_child) => child = Some(new_child),
+                Err(e) => error!("插件 {} 重启失败: {}", self.plugin_id, e),
+            }
+        }
+    }
+
+    /**
+     * 读取插件输出直到进程退出或被停止
+     */
+    async fn run_child(self: &Arc<Self>, mut child: Child) {
+        if let Some(stdin) = child.stdin.take() {
+            *self.stdin.lock().await = Some(stdin);
+        }
+
+        if let Some(stderr) = child.stderr.take() {
+            let plugin_id = self.plugin_id.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut reader = BufReader::new(stderr);
+                let mut buf = Vec::new();
+                loop {
+                    match read_line_limited(&mut reader, &mut buf).await {
+                        Ok(LineRead::Line) => {
+                            warn!("[plugin:{}] {}", plugin_id, String::from_utf8_lossy(&buf).trim_end())
+                        }
+                        Ok(LineRead::TooLarge) => warn!("[plugin:{}] 错误输出过长，已丢弃", plugin_id),
+                        _ => break,
+                    }
+                }
+            });
+        }
+
+        if let Some(stdout) = child.stdout.take() {
+            let mut reader = BufReader::new(stdout);
+            let mut buf = Vec::new();
+            loop {
+                tokio::select! {
+                    line = read_line_limited(&mut reader, &mut buf) => match line {
+                        Ok(LineRead::Line) => {
+                            let line = String::from_utf8_lossy(&buf).to_string();
+                            self.handle_line(&line);
+                        }
+                        Ok(LineRead::TooLarge) => self.handle_oversized(&buf),
+                        Ok(LineRead::Eof) => break,
+                        Err(e) => {
+                            error!("读取插件 {} 输出失败: {}", self.plugin_id, e);
+                            break;
+                        }
+                    },
+                    _ = self.shutdown.notified() => {
+                        let _ = child.kill().await;
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.stdin.lock().await.take();
+        match child.wait().await {
+            Ok(status) => info!("插件 {} 进程退出: {}", self.plugin_id, status),
+            Err(e) => error!("等待插件 {} 进程退出失败: {}", self.plugin_id, e),
+        }
+    }
+
+    /**
+     * 处理插件输出的一行
+     */
+    fn handle_line(self: &Arc<Self>, line: &str) {
+        let message = match rpc::decode_message(line) {
+            Some(message) => message,
+            None => {
+                info!("[plugin:{}] {}", self.plugin_id, line.trim_end());
+                return;
+            }
+        };
+
+        if message.is_response() {
+            let id = message.id.unwrap();
+            let result = match message.error {
+                Some(e) => Err(PluginError::Plugin {
+                    code: e.code,
+                    message: e.message,
+                    data: e.data,
+                }),
+                None => Ok(message.result.unwrap_or(Value::Null)),
+            };
+            self.resolve(id, result);
+            return;
+        }
+
+        if let (Some(id), Some(method)) = (message.id, message.method) {
+            let params = message.params.unwrap_or(Value::Null);
+            // 宿主接口可能读写文件或剪贴板，在后台线程中处理，不阻塞读取插件输出
+            let process = self.clone();
+            tauri::async_runtime::spawn(async move {
+                let handler = process.spec.handler.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || handler(&method, params))
+                    .await
+                    .unwrap_or_else(|e| {
+                        Err(RpcError {
+                            code: rpc::INTERNAL_ERROR,
+                            message: e.to_string(),
+                            data: None,
+                        })
+                    });
+                let response = match result {
+                    Ok(result) => serde_json::json!({
+                        "jsonrpc": rpc::JSONRPC_VERSION,
+                        "id": id,
+                        "result": result,
+                    }),
+                    Err(error) => serde_json::json!({
+                        "jsonrpc": rpc::JSONRPC_VERSION,
+                        "id": id,
+                        "error": error,
+                    }),
+                };
+                let _ = process.write_line(&format!("{}\n", response)).await;
+            });
+        }
+    }
+
+    /**
+     * 处理超过大小限制的输出，尽量让对应的调用返回错误
+     */
+    fn handle_oversized(&self, prefix: &[u8]) {
+        match rpc::peek_id(prefix) {
+            Some(id) => self.resolve(
+                id,
+                Err(PluginError::OutputTooLarge {
+                    limit: MAX_MESSAGE_SIZE,
+                }),
+            ),
+            None => {
+                // 无法确定对应的调用，让所有等待中的调用返回错误
+                warn!("插件 {} 输出过长，已丢弃", self.plugin_id);
+                self.fail_pending(PluginError::OutputTooLarge {
+                    limit: MAX_MESSAGE_SIZE,
+                });
+            }
+        }
+    }
+
+    /**
+     * 将结果交给等待中的调用
+     */
+    fn resolve(&self, id: u64, result: Result<Value, PluginError>) {
+        let sender = self.pending.lock().unwrap().remove(&id);
+        match sender {
+            Some(sender) => {
+                let _ = sender.send(result);
+            }
+            None => warn!("插件 {} 返回了未知的请求id: {}", self.plugin_id, id),
+        }
+    }
+
+    /**
+     * 向插件写入一行
+     */
+    async fn write_line(&self, line: &str) -> Result<(), PluginError> {
+        let mut stdin = self.stdin.lock().await;
+        match stdin.as_mut() {
+            Some(stdin) => {
+                stdin
+                    .write_all(line.as_bytes())
+                    .await
+                    .map_err(|e| PluginError::Io {
+                        message: e.to_string(),
+                    })?;
+                stdin.flush().await.map_err(|e| PluginError::Io {
+                    message: e.to_string(),
+                })
+            }
+            None => Err(PluginError::NotRunning),
+        }
+    }
+
+    /**
+     * 让所有等待中的调用返回错误
+     */
+    fn fail_pending(&self, error: PluginError) {
+        let pending: Vec<_> = self.pending.lock().unwrap().drain().collect();
+        for (_, sender) in pending {
+            let _ = sender.send(Err(error.clone()));
+        }
+    }
+}
+
+/**
+ * 读取一行输出，超过大小限制的行会被丢弃剩余部分
+ */
+async fn read_line_limited<R>(reader: &mut R, buf: &mut Vec<u8>) -> std::io::Result<LineRead>
+where
+    R: AsyncBufRead + Unpin,
+{
+    buf.clear();
+    let read = (&mut *reader)
+        .take(MAX_MESSAGE_SIZE as u64)
+        .read_until(b'\n', buf)
+        .await?;
+    if read == 0 {
+        return Ok(LineRead::Eof);
+    }
+    if buf.ends_with(b"\n") || read < MAX_MESSAGE_SIZE {
+        return Ok(LineRead::Line);
+    }
+
+    // 丢弃该行剩余的内容
+    let mut discard = Vec::new();
+    loop {
+        discard.clear();
+        let read = (&mut *reader)
+            .take(64 * 1024)
+            .read_until(b'\n', &mut discard)
+            .await?;
+        if read == 0 || discard.ends_with(b"\n") {
+            return Ok(LineRead::TooLarge);
+        }
+    }
+}
+
+/**
+ * 创建插件子进程
+ */
+fn spawn_child(plugin_id: &str, spec: &LaunchSpec) -> Result<Child, PluginError> {
+    info!("启动插件进程: {}, {:?}", plugin_id, spec.program);
+    let mut command = Command::new(&spec.program);
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .env_clear();
+    // 只传递运行必需的环境变量
+    for key in INHERITED_ENVS {
+        if let Some(value) = std::env::var_os(key) {
+            command.env(key, value);
+        }
+    }
+    for (key, value) in &spec.envs {
+        command.env(key, value);
+    }
+
+    #[cfg(target_os = "linux")]
+    let sandbox_pipe = SandboxPipe::new().map_err(|e| PluginError::SpawnFailed {
+        message: e.to_string(),
+    })?;
+    #[cfg(target_os = "linux")]
+    apply_sandbox(
+        &mut command,
+        spec.limits.clone(),
+        spec.allow_network,
+        sandbox_pipe.write.as_raw_fd(),
+    );
+
+    command.spawn().map_err(|e| {
+        #[cfg(target_os = "linux")]
+        let sandbox_failed = sandbox_pipe.failed();
+        #[cfg(not(target_os = "linux"))]
+        let sandbox_failed = false;
+        spawn_error(plugin_id, e, sandbox_failed)
+    })
+}
+
+/**
+ * 启动失败的原因，sandbox_failed 表示失败发生在创建网络隔离沙箱时
+ */
+fn spawn_error(plugin_id: &str, e: std::io::Error, sandbox_failed: bool) -> PluginError {
+    if sandbox_failed {
+        error!("插件 {} 的网络隔离沙箱创建失败，拒绝启动: {}", plugin_id, e);
+        return PluginError::SpawnFailed {
+            message: format!(
+                "无法创建网络隔离沙箱，系统需要支持非特权用户命名空间，插件没有网络权限，拒绝启动: {}",
+                e
+            ),
+        };
+    }
+    PluginError::SpawnFailed {
+        message: e.to_string(),
+    }
+}
+
+/**
+ * 子进程创建沙箱失败时向管道写入一个字节，用于区分沙箱失败和其他启动失败
+ */
+#[cfg(target_os = "linux")]
+struct SandboxPipe {
+    read: OwnedFd,
+    write: OwnedFd,
+}
+
+#[cfg(target_os = "linux")]
+impl SandboxPipe {
+    fn new() -> std::io::Result<Self> {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC | libc::O_NONBLOCK) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        unsafe {
+            Ok(SandboxPipe {
+                read: OwnedFd::from_raw_fd(fds[0]),
+                write: OwnedFd::from_raw_fd(fds[1]),
+            })
+        }
+    }
+
+    /**
+     * 子进程是否报告了沙箱创建失败
+     */
+    fn failed(&self) -> bool {
+        let mut byte = 0u8;
+        let read = unsafe {
+            libc::read(
+                self.read.as_raw_fd(),
+                &mut byte as *mut u8 as *mut libc::c_void,
+                1,
+            )
+        };
+        read == 1
+    }
+}
+
+/**
+ * 在子进程执行前设置资源限制和沙箱，沙箱创建失败时向 sandbox_fd 写入一个字节
+ */
+#[cfg(target_os = "linux")]
+fn apply_sandbox(
+    command: &mut Command,
+    limits: ResourceLimits,
+    allow_network: bool,
+    sandbox_fd: RawFd,
+) {
+    // 在新的用户命名空间中把当前用户映射为自己，保证插件仍能访问用户的文件
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    let uid_map = format!("{0} {0} 1", uid).into_bytes();
+    let gid_map = format!("{0} {0} 1", gid).into_bytes();
+
+    // pre_exec 中只调用 setrlimit、prctl、unshare、open、write 等 async-signal-safe 的系统调用
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(seconds) = limits.lifetime_cpu_seconds {
+                let limit = libc::rlimit {
+                    rlim_cur: seconds as libc::rlim_t,
+                    rlim_max: seconds as libc::rlim_t,
+                };
+                if libc::setrlimit(libc::RLIMIT_CPU, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            if let Some(bytes) = limits.memory_bytes {
+                let limit = libc::rlimit {
+                    rlim_cur: bytes as libc::rlim_t,
+                    rlim_max: bytes as libc::rlim_t,
+                };
+                if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            // 禁止插件通过 setuid 程序提权
+            if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // 没有网络权限时放入独立的网络命名空间，无法创建时拒绝启动，不能在没有隔离的情况下运行
+            if !allow_network {
+                if let Err(e) = enter_network_sandbox(&uid_map, &gid_map) {
+                    libc::write(sandbox_fd, [1u8].as_ptr() as *const libc::c_void, 1);
+                    return Err(e);
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+/**
+ * 进入新的用户和网络命名空间，在 pre_exec 中调用
+ */
+#[cfg(target_os = "linux")]
+unsafe fn enter_network_sandbox(uid_map: &[u8], gid_map: &[u8]) -> std::io::Result<()> {
+    if libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    write_proc_file(c"/proc/self/setgroups", b"deny")?;
+    write_proc_file(c"/proc/self/uid_map", uid_map)?;
+    write_proc_file(c"/proc/self/gid_map", gid_map)
+}
+
+/**
+ * 在 pre_exec 中写入 /proc 文件，不分配内存，打开或写入不完整时返回错误
+ */
+#[cfg(target_os = "linux")]
+unsafe fn write_proc_file(path: &std::ffi::CStr, content: &[u8]) -> std::io::Result<()> {
+    let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let written = libc::write(fd, content.as_ptr() as *const libc::c_void, content.len());
+    let result = if written == content.len() as isize {
+        Ok(())
+    } else if written < 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Err(std::io::Error::from_raw_os_error(libc::EIO))
+    };
+    libc::close(fd);
+    result
+}
+
+/**
+ * 计算第 n 次重启的退避时长
+ */
+fn backoff_delay(restarts: u32) -> Duration {
+    let factor = 2u32.saturating_pow(restarts.saturating_sub(1));
+    BACKOFF_BASE.saturating_mul(factor).min(BACKOFF_MAX)
+}
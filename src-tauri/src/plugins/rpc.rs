@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+/**
+ * 宿主内部错误的错误码
+ */
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/**
+ * 宿主发给插件的请求
+ */
+#[derive(Serialize)]
+pub struct RpcRequest<'a> {
+    pub jsonrpc: &'static str,
+    pub id: u64,
+    pub method: &'a str,
+    pub params: &'a Value,
+}
+
+/**
+ * 宿主发给插件的通知（没有id，不需要响应）
+ */
+#[derive(Serialize)]
+pub struct RpcNotification<'a> {
+    pub jsonrpc: &'static str,
+    pub method: &'a str,
+    pub params: Value,
+}
+
+/**
+ * 插件返回的错误
+ */
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+/**
+ * 插件输出的一行消息
+ */
+#[derive(Deserialize)]
+pub struct RpcMessage {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub params: Option<Value>,
+    #[serde(default)]
+    pub result: Option<Value>,
+    #[serde(default)]
+    pub error: Option<RpcError>,
+}
+
+impl RpcMessage {
+    /**
+     * 是否是对宿主请求的响应
+     */
+    pub fn is_response(&self) -> bool {
+        self.method.is_none() && self.id.is_some()
+    }
+}
+
+/**
+ * 序列化请求，每个请求占一行
+ */
+pub fn encode_request(id: u64, method: &str, params: &Value) -> String {
+    let request = RpcRequest {
+        jsonrpc: JSONRPC_VERSION,
+        id,
+        method,
+        params,
+    };
+    let mut line = serde_json::to_string(&request).expect("插件请求序列化失败");
+    line.push('\n');
+    line
+}
+
+/**
+ * 序列化通知，每个通知占一行
+ */
+pub fn encode_notification(method: &str, params: Value) -> String {
+    let notification = RpcNotification {
+        jsonrpc: JSONRPC_VERSION,
+        method,
+        params,
+    };
+    let mut line = serde_json::to_string(&notification).expect("插件通知序列化失败");
+    line.push('\n');
+    line
+}
+
+/**
+ * 解析插件输出的一行，不是 JSON-RPC 消息的行（第三方库的额外输出等）返回 None
+ */
+pub fn decode_message(line: &str) -> Option<RpcMessage> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    serde_json::from_str::<RpcMessage>(line).ok()
+}
+
+/**
+ * 从被截断的输出中找出请求id，用于让超长响应对应的调用返回错误，只认最外层对象中的 id
+ */
+pub fn peek_id(prefix: &[u8]) -> Option<u64> {
+    let text = String::from_utf8_lossy(prefix);
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.checked_sub(1)?,
+            b'"' => {
+                let end = string_end(bytes, i + 1)?;
+                if depth == 1 && &bytes[i + 1..end] == b"id" {
+                    let rest = text[end + 1..].trim_start();
+                    if let Some(value) = rest.strip_prefix(':') {
+                        let value = value.trim_start();
+                        let digits: String =
+                            value.chars().take_while(|c| c.is_ascii_digit()).collect();
+                        return digits.parse().ok();
+                    }
+                }
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/**
+ * 返回从 start 开始的字符串结束引号的位置，字符串被截断时返回 None
+ */
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
@@ -1,16 +1,20 @@
 use crate::listener;
+use crate::paste;
 use crate::tray::state::TRAY_STATE;
+use crate::windows;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
 /**
  * 鼠标点击事件处理
  */
 pub fn handle_tray_click(tray: &tauri::tray::TrayIcon) {
-    let mut state = TRAY_STATE.lock().unwrap();
-
-    if state.win_enter_visible && !state.win_current_visible && state.is_first_hide {
-        state.is_first_hide = false;
-        return;
+    {
+        let mut state = TRAY_STATE.lock().unwrap();
+        if state.win_enter_visible && !state.win_current_visible && state.is_first_hide {
+            state.is_first_hide = false;
+            return;
+        }
     }
 
     println!("鼠标松开");
@@ -22,18 +26,14 @@ pub fn handle_tray_click(tray: &tauri::tray::TrayIcon) {
         Ok(visible) => {
             if !visible {
                 println!("显示窗口");
+                paste::remember_target_window();
+                // 计算打开位置时会读取托盘状态，需要在加锁前调用
+                windows::place_list_window(tray.app_handle(), &win);
                 win.show().expect("窗口显示失败");
                 win.unminimize().expect("窗口取消最小化失败");
                 win.set_focus().expect("窗口聚焦失败");
-                match &win.outer_position() {
-                    Ok(position) => {
-                        if position.x == -1000 && position.y == -1000 {
-                            win.center().expect("居中窗口失败");
-                        }
-                    }
-                    _ => {}
-                }
 
+                let mut state = TRAY_STATE.lock().unwrap();
                 state.win_current_visible = true;
                 state.win_enter_visible = true;
                 state.is_first_hide = false;
@@ -44,6 +44,7 @@ pub fn handle_tray_click(tray: &tauri::tray::TrayIcon) {
                 println!("隐藏窗口");
                 win.hide().expect("窗口隐藏失败");
 
+                let mut state = TRAY_STATE.lock().unwrap();
                 state.win_current_visible = false;
                 state.win_enter_visible = false;
                 state.is_first_hide = true;
@@ -84,6 +85,10 @@ pub fn handle_menu_event(app: &AppHandle, event_id: &str) {
                 println!("开始监听剪贴板")
             }
         }
+        "pause_5m" => listener::pause_listening(app.clone(), Some(Duration::from_secs(5 * 60))),
+        "pause_1h" => listener::pause_listening(app.clone(), Some(Duration::from_secs(60 * 60))),
+        "pause_until_restart" => listener::pause_listening(app.clone(), None),
+        "clear_paste_queue" => paste::clear_queue(app),
         "check_update" => {
             app.emit("check-update", "".to_string()).unwrap();
         }
@@ -1,6 +1,7 @@
 use crate::i18n::I18nState;
 use crate::listener;
-use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem};
+use crate::paste;
+use tauri::menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tauri::{AppHandle, Manager, Wry};
 
 /**
@@ -20,6 +21,53 @@ pub fn build_tray_menu(app: &AppHandle) -> Menu<Wry> {
         None::<&str>,
     )
     .unwrap();
+    let listening_status = listener::listening_status();
+    let pause_listening = Submenu::with_id_and_items(
+        app,
+        "pause_listening",
+        &language.pause_listening,
+        matches!(listening_status, listener::ListeningStatus::Listening),
+        &[
+            &MenuItem::with_id(
+                app,
+                "pause_5m",
+                &language.pause_five_minutes,
+                true,
+                None::<&str>,
+            )
+            .unwrap(),
+            &MenuItem::with_id(
+                app,
+                "pause_1h",
+                &language.pause_one_hour,
+                true,
+                None::<&str>,
+            )
+            .unwrap(),
+            &MenuItem::with_id(
+                app,
+                "pause_until_restart",
+                &language.pause_until_restart,
+                true,
+                None::<&str>,
+            )
+            .unwrap(),
+        ],
+    )
+    .unwrap();
+    // 暂停时显示剩余时间
+    let paused_text = match listening_status {
+        listener::ListeningStatus::Paused {
+            remaining_secs: Some(secs),
+            ..
+        } => language
+            .paused_remaining
+            .replace("{minutes}", &secs.div_ceil(60).max(1).to_string()),
+        listener::ListeningStatus::Paused { .. } => language.paused_until_restart.clone(),
+        _ => String::new(),
+    };
+    let paused_status =
+        MenuItem::with_id(app, "paused_status", &paused_text, false, None::<&str>).unwrap();
     let check_update = MenuItem::with_id(
         app,
         "check_update",
@@ -31,22 +79,43 @@ pub fn build_tray_menu(app: &AppHandle) -> Menu<Wry> {
     let about = MenuItem::with_id(app, "about", &language.about, true, None::<&str>).unwrap();
     let restart = MenuItem::with_id(app, "restart", &language.restart, true, None::<&str>).unwrap();
     let exit = MenuItem::with_id(app, "exit", &language.exit, true, None::<&str>).unwrap();
-    let separator = PredefinedMenuItem::separator(app).unwrap();
-
-    Menu::with_items(
+    let queue_len = paste::queue_len();
+    let paste_queue = MenuItem::with_id(
         app,
-        &[
-            &settings,
-            &clipboard_monitor,
-            &separator,
-            &check_update,
-            &about,
-            &separator,
-            &restart,
-            &exit,
-        ],
+        "paste_queue",
+        format!("{}: {}", language.paste_queue, queue_len),
+        false,
+        None::<&str>,
+    )
+    .unwrap();
+    let clear_paste_queue = MenuItem::with_id(
+        app,
+        "clear_paste_queue",
+        &language.clear_paste_queue,
+        queue_len > 0,
+        None::<&str>,
     )
-    .unwrap()
+    .unwrap();
+    let separator = PredefinedMenuItem::separator(app).unwrap();
+
+    let mut items: Vec<&dyn IsMenuItem<Wry>> = vec![&settings, &clipboard_monitor];
+    if paused_text.is_empty() {
+        items.push(&pause_listening);
+    } else {
+        items.push(&paused_status);
+    }
+    items.extend_from_slice(&[
+        &separator,
+        &paste_queue,
+        &clear_paste_queue,
+        &separator,
+        &check_update,
+        &about,
+        &separator,
+        &restart,
+        &exit,
+    ]);
+    Menu::with_items(app, &items).unwrap()
 }
 
 /**
@@ -3,11 +3,12 @@ mod event_handler;
 mod menu_builder;
 mod state;
 
+use crate::paste;
 use crate::tray::event_handler::*;
 use crate::tray::menu_builder::build_tray_menu;
 use crate::tray::state::TRAY_STATE;
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
-use tauri::{AppHandle};
+use tauri::AppHandle;
 
 pub use commands::*;
 
@@ -21,13 +22,14 @@ pub fn create_tray(app: AppHandle) {
     if state.is_loaded {
         if let Some(tray) = state.tray.take() {
             tray.set_menu(Some(menu)).unwrap();
+            let _ = tray.set_tooltip(Some(tooltip()));
             state.tray = Some(tray);
             return;
         }
     }
 
     let tray = TrayIconBuilder::new()
-        .tooltip("EasyPaste")
+        .tooltip(tooltip())
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
         .show_menu_on_left_click(false)
@@ -50,6 +52,16 @@ pub fn create_tray(app: AppHandle) {
     state.is_first_hide = false;
 }
 
+/**
+ * 托盘提示，粘贴队列不为空时显示剩余数量
+ */
+fn tooltip() -> String {
+    match paste::queue_len() {
+        0 => "EasyPaste".to_string(),
+        len => format!("EasyPaste ({})", len),
+    }
+}
+
 /**
  * 重新加载托盘菜单
  */
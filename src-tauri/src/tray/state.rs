@@ -1,6 +1,6 @@
+use lazy_static::lazy_static;
 use std::sync::{Arc, Mutex};
 use tauri::tray::TrayIcon;
-use lazy_static::lazy_static;
 
 #[derive(Default)]
 pub struct TrayState {
@@ -12,6 +12,5 @@ pub struct TrayState {
 }
 
 lazy_static! {
-    pub static ref TRAY_STATE: Arc<Mutex<TrayState>> = 
-        Arc::new(Mutex::new(TrayState::default()));
+    pub static ref TRAY_STATE: Arc<Mutex<TrayState>> = Arc::new(Mutex::new(TrayState::default()));
 }
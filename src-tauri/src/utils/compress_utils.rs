@@ -1,70 +1,414 @@
 use crate::models::file_model::TreeOption;
 use flate2::read::GzDecoder;
+use serde::Serialize;
+use sevenz_rust::SevenZMethod;
+use std::fmt;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use unrar::error::Code;
 use unrar::Archive;
 
+/**
+ * 最多读取的条目数量，超过后只返回已读取的部分
+ */
+const MAX_ENTRIES: usize = 10_000;
+/**
+ * 读取目录信息的最长时间，超过后只返回已读取的部分
+ */
+const MAX_DURATION: Duration = Duration::from_secs(5);
+/**
+ * 7z 目录信息的最大大小，超过时直接拒绝
+ */
+const MAX_7Z_HEADER_SIZE: u64 = 16 * 1024 * 1024;
+
+/**
+ * 读取压缩文件失败的原因
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ArchiveError {
+    /**
+     * 文件无法打开
+     */
+    Open { message: String },
+    /**
+     * 文件已损坏或不是对应的压缩格式
+     */
+    Corrupt { message: String },
+    /**
+     * 目录信息已加密，需要密码才能读取
+     */
+    PasswordRequired,
+    /**
+     * 只读取了部分条目，entries 为已读取的部分
+     */
+    Partial {
+        entries: Vec<TreeOption>,
+        reason: Truncation,
+    },
+}
+
+/**
+ * 只读取了部分条目的原因
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum Truncation {
+    /**
+     * 条目数量超过限制
+     */
+    TooManyEntries { limit: usize },
+    /**
+     * 读取时间超过限制
+     */
+    Timeout { seconds: u64 },
+    /**
+     * 读取到损坏的条目
+     */
+    Corrupt { message: String },
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveError::Open { message } => write!(f, "无法打开压缩文件: {}", message),
+            ArchiveError::Corrupt { message } => write!(f, "压缩文件已损坏: {}", message),
+            ArchiveError::PasswordRequired => write!(f, "压缩文件的目录已加密"),
+            ArchiveError::Partial { entries, reason } => {
+                write!(f, "只读取了 {} 个条目: ", entries.len())?;
+                match reason {
+                    Truncation::TooManyEntries { limit } => write!(f, "条目超过 {} 个", limit),
+                    Truncation::Timeout { seconds } => write!(f, "读取超过 {} 秒", seconds),
+                    Truncation::Corrupt { message } => write!(f, "{}", message),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+/**
+ * 收集读取到的条目，检查数量和时间限制
+ */
+struct Listing {
+    entries: Vec<TreeOption>,
+    started: Instant,
+}
+
+impl Listing {
+    fn new() -> Self {
+        Listing {
+            entries: Vec::new(),
+            started: Instant::now(),
+        }
+    }
+
+    /**
+     * 读取下一个条目前检查是否超过限制
+     */
+    fn check(&self) -> Result<(), Truncation> {
+        if self.entries.len() >= MAX_ENTRIES {
+            return Err(Truncation::TooManyEntries { limit: MAX_ENTRIES });
+        }
+        if self.started.elapsed() > MAX_DURATION {
+            return Err(Truncation::Timeout {
+                seconds: MAX_DURATION.as_secs(),
+            });
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, entry: TreeOption) {
+        self.entries.push(entry);
+    }
+
+    /**
+     * 读取完成，按路径长度排序，保证父目录在子条目之前
+     */
+    fn finish(mut self) -> Vec<TreeOption> {
+        self.entries.sort_by_key(|entry| entry.path.len());
+        self.entries
+    }
+
+    /**
+     * 提前结束读取，还没有读取到条目时直接返回损坏的错误
+     */
+    fn truncate(self, reason: Truncation) -> ArchiveError {
+        match reason {
+            Truncation::Corrupt { message } if self.entries.is_empty() => {
+                ArchiveError::Corrupt { message }
+            }
+            reason => ArchiveError::Partial {
+                entries: self.finish(),
+                reason,
+            },
+        }
+    }
+}
+
+/**
+ * 每次读取前检查是否超时，避免解压单个很大的条目时超过时间限制
+ */
+struct DeadlineReader<R> {
+    inner: R,
+    started: Instant,
+}
+
+impl<R: Read> Read for DeadlineReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.started.elapsed() > MAX_DURATION {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "读取超时"));
+        }
+        self.inner.read(buf)
+    }
+}
+
+fn open(path: &str) -> Result<File, ArchiveError> {
+    File::open(path).map_err(|e| ArchiveError::Open {
+        message: e.to_string(),
+    })
+}
+
+fn corrupt(e: impl fmt::Display) -> Truncation {
+    Truncation::Corrupt {
+        message: e.to_string(),
+    }
+}
+
 /**
  * 解析rar文件
  */
-pub fn parse_rar(path: &str) -> Vec<TreeOption> {
-    let mut data_list = Vec::new();
-    for entry in Archive::new(path).open_for_listing().unwrap() {
-        let file_info = entry.unwrap();
-        data_list.push(TreeOption {
-            path: file_info
-                .filename
-                .to_str()
-                .unwrap()
-                .to_string()
-                .replace("\\", "/"),
+pub fn parse_rar(path: &str) -> Result<Vec<TreeOption>, ArchiveError> {
+    let archive = Archive::new(path)
+        .open_for_listing()
+        .map_err(|e| match e.code {
+            Code::MissingPassword | Code::BadPassword => ArchiveError::PasswordRequired,
+            Code::EOpen => ArchiveError::Open {
+                message: e.to_string(),
+            },
+            _ => ArchiveError::Corrupt {
+                message: e.to_string(),
+            },
+        })?;
+    let mut listing = Listing::new();
+    for entry in archive {
+        if let Err(reason) = listing.check() {
+            return Err(listing.truncate(reason));
+        }
+        let file_info = match entry {
+            Ok(file_info) => file_info,
+            Err(e) => return Err(listing.truncate(corrupt(e))),
+        };
+        listing.push(TreeOption {
+            path: file_info.filename.to_string_lossy().replace("\\", "/"),
             dir: file_info.is_directory(),
             date: file_info.file_time as u64,
             size: file_info.unpacked_size,
+            encrypted: file_info.is_encrypted(),
         });
     }
-    data_list.sort_by(|a, b| a.path.len().cmp(&b.path.len()));
-    data_list
+    Ok(listing.finish())
 }
 
 /**
- * 解析tar文件
+ * 解析tar文件，跳过文件内容直接读取下一个条目
  */
-pub fn parse_tar(path: &str) -> Vec<TreeOption> {
-    let mut data_list = Vec::new();
-    tar::Archive::new(File::open(path).unwrap())
-        .entries()
-        .unwrap()
-        .for_each(|entry| {
-            let entry = entry.unwrap();
-            data_list.push(TreeOption {
-                path: entry.path().unwrap().to_str().unwrap().to_string(),
-                dir: entry.header().entry_type().is_dir(),
-                date: entry.header().mtime().unwrap(),
-                size: entry.header().size().unwrap(),
-            });
-        });
-    data_list
+pub fn parse_tar(path: &str) -> Result<Vec<TreeOption>, ArchiveError> {
+    let mut archive = tar::Archive::new(open(path)?);
+    let entries = archive
+        .entries_with_seek()
+        .map_err(|e| ArchiveError::Corrupt {
+            message: e.to_string(),
+        })?;
+    list_tar_entries(entries)
 }
 
 /**
  * 解析gzip文件
  */
-pub fn parse_gzip(path: &str) -> Vec<TreeOption> {
-    let mut data_list = Vec::new();
-    let file = File::open(path).unwrap();
-    let buf_reader = BufReader::new(file);
-    let gz_decoder = GzDecoder::new(buf_reader);
-    let mut archive = tar::Archive::new(gz_decoder);
-
-    archive.entries().unwrap().for_each(|entry| {
-        let entry = entry.unwrap();
-        data_list.push(TreeOption {
-            path: entry.path().unwrap().to_str().unwrap().to_string(),
-            dir: entry.header().entry_type().is_dir(),
-            date: entry.header().mtime().unwrap(),
-            size: entry.header().size().unwrap(),
+pub fn parse_gzip(path: &str) -> Result<Vec<TreeOption>, ArchiveError> {
+    let gz_decoder = GzDecoder::new(BufReader::new(open(path)?));
+    let mut archive = tar::Archive::new(DeadlineReader {
+        inner: gz_decoder,
+        started: Instant::now(),
+    });
+    let entries = archive.entries().map_err(|e| ArchiveError::Corrupt {
+        message: e.to_string(),
+    })?;
+    list_tar_entries(entries)
+}
+
+fn list_tar_entries<R: Read>(
+    entries: tar::Entries<'_, R>,
+) -> Result<Vec<TreeOption>, ArchiveError> {
+    let mut listing = Listing::new();
+    for entry in entries {
+        if let Err(reason) = listing.check() {
+            return Err(listing.truncate(reason));
+        }
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+                return Err(listing.truncate(Truncation::Timeout {
+                    seconds: MAX_DURATION.as_secs(),
+                }))
+            }
+            Err(e) => return Err(listing.truncate(corrupt(e))),
+        };
+        let header = entry.header();
+        listing.push(TreeOption {
+            path: String::from_utf8_lossy(&entry.path_bytes()).into_owned(),
+            dir: header.entry_type().is_dir(),
+            date: header.mtime().unwrap_or(0),
+            size: header.size().unwrap_or(0),
+            encrypted: false,
+        });
+    }
+    Ok(listing.finish())
+}
+
+/**
+ * 解析zip文件，只读取目录信息，不解压
+ */
+pub fn parse_zip(path: &str) -> Result<Vec<TreeOption>, ArchiveError> {
+    let mut archive =
+        zip::ZipArchive::new(BufReader::new(open(path)?)).map_err(|e| ArchiveError::Corrupt {
+            message: e.to_string(),
+        })?;
+    let mut listing = Listing::new();
+    for index in 0..archive.len() {
+        if let Err(reason) = listing.check() {
+            return Err(listing.truncate(reason));
+        }
+        let entry = match archive.by_index_raw(index) {
+            Ok(entry) => entry,
+            Err(e) => return Err(listing.truncate(corrupt(e))),
+        };
+        // 没有标记 UTF-8 的文件名，能按 UTF-8 解析时优先使用，否则按 CP437 处理
+        let name = match std::str::from_utf8(entry.name_raw()) {
+            Ok(name) => name.to_string(),
+            Err(_) => entry.name().to_string(),
+        };
+        listing.push(TreeOption {
+            path: name.replace("\\", "/"),
+            dir: entry.is_dir(),
+            date: entry.last_modified().map_or(0, dos_time_to_unix),
+            size: entry.size(),
+            encrypted: entry.encrypted(),
+        });
+    }
+    Ok(listing.finish())
+}
+
+/**
+ * 解析7z文件，只读取目录信息，不解压
+ * 7z 的目录信息需要一次读取完成，读取前先检查目录信息的大小，读取超时后不再等待
+ */
+pub fn parse_7z(path: &str) -> Result<Vec<TreeOption>, ArchiveError> {
+    let mut file = open(path)?;
+    let len = file
+        .metadata()
+        .map_err(|e| ArchiveError::Open {
+            message: e.to_string(),
+        })?
+        .len();
+    // 起始头的 20..28 字节为目录信息的大小
+    let mut start_header = [0u8; 32];
+    file.read_exact(&mut start_header)
+        .and_then(|_| file.seek(SeekFrom::Start(0)))
+        .map_err(|e| ArchiveError::Corrupt {
+            message: e.to_string(),
+        })?;
+    let header_size = u64::from_le_bytes(start_header[20..28].try_into().unwrap());
+    if header_size > MAX_7Z_HEADER_SIZE {
+        return Err(ArchiveError::Corrupt {
+            message: format!("目录信息过大: {} 字节", header_size),
         });
+    }
+
+    let mut listing = Listing::new();
+    // 在单独的线程中读取，超时后该线程读取完成时结果直接丢弃
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = tx.send(sevenz_rust::Archive::read(&mut file, len, &[]));
     });
-    data_list
+    let archive = match rx.recv_timeout(MAX_DURATION) {
+        Ok(result) => result.map_err(|e| match e {
+            sevenz_rust::Error::PasswordRequired | sevenz_rust::Error::MaybeBadPassword(_) => {
+                ArchiveError::PasswordRequired
+            }
+            e => ArchiveError::Corrupt {
+                message: e.to_string(),
+            },
+        })?,
+        Err(_) => {
+            return Err(listing.truncate(Truncation::Timeout {
+                seconds: MAX_DURATION.as_secs(),
+            }))
+        }
+    };
+    for (index, entry) in archive.files.iter().enumerate() {
+        if let Err(reason) = listing.check() {
+            return Err(listing.truncate(reason));
+        }
+        // 条目所在的数据块使用了 AES 编码即为加密
+        let encrypted = archive
+            .stream_map
+            .file_folder_index
+            .get(index)
+            .copied()
+            .flatten()
+            .and_then(|folder| archive.folders.get(folder))
+            .is_some_and(|folder| {
+                folder
+                    .coders
+                    .iter()
+                    .any(|coder| coder.decompression_method_id() == SevenZMethod::ID_AES256SHA256)
+            });
+        let date = if entry.has_last_modified_date {
+            SystemTime::from(entry.last_modified_date())
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs())
+        } else {
+            0
+        };
+        listing.push(TreeOption {
+            path: entry.name().replace("\\", "/"),
+            dir: entry.is_directory(),
+            date,
+            size: entry.size(),
+            encrypted,
+        });
+    }
+    Ok(listing.finish())
+}
+
+/**
+ * zip 中的 DOS 时间转换为时间戳，单位：秒，按 UTC 计算
+ */
+fn dos_time_to_unix(time: zip::DateTime) -> u64 {
+    let (year, month, day) = (time.year() as i64, time.month() as i64, time.day() as i64);
+    // 以三月为一年的开始计算天数，闰日落在年末
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+    let seconds =
+        days * 86400 + time.hour() as i64 * 3600 + time.minute() as i64 * 60 + time.second() as i64;
+    seconds.max(0) as u64
 }
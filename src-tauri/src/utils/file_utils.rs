@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/**
+ * 快速哈希读取的文件头部和尾部长度
+ */
+const HASH_CHUNK: u64 = 64 * 1024;
+/**
+ * 文件夹的 MIME 类型
+ */
+const DIRECTORY_MIME: &str = "inode/directory";
+
+/**
+ * 复制的文件的元数据，随剪贴板记录一起保存，用于展示和判断文件是否被移动或删除
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetadata {
+    pub path: String,
+    /**
+     * 采集时文件是否存在
+     */
+    pub exists: bool,
+    pub is_dir: bool,
+    /**
+     * 文件大小，单位：字节，文件夹为 0
+     */
+    pub size: u64,
+    /**
+     * 修改时间的时间戳，单位：毫秒
+     */
+    pub modified: Option<u64>,
+    pub mime: Option<String>,
+    /**
+     * 文件夹中直接包含的文件和文件夹数量
+     */
+    pub item_count: Option<u64>,
+    /**
+     * 快速哈希，只计算文件大小、头部和尾部，文件夹为空
+     */
+    pub hash: Option<String>,
+}
+
+/**
+ * 采集文件的元数据，文件不存在或无法访问时 exists 为 false
+ */
+pub fn collect(path: &str) -> FileMetadata {
+    let mut metadata = FileMetadata {
+        path: path.to_string(),
+        exists: false,
+        is_dir: false,
+        size: 0,
+        modified: None,
+        mime: None,
+        item_count: None,
+        hash: None,
+    };
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(_) => return metadata,
+    };
+
+    metadata.exists = true;
+    metadata.is_dir = meta.is_dir();
+    metadata.modified = meta
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64);
+    if metadata.is_dir {
+        metadata.mime = Some(DIRECTORY_MIME.to_string());
+        metadata.item_count = fs::read_dir(path).ok().map(|dir| dir.count() as u64);
+    } else {
+        metadata.size = meta.len();
+        metadata.mime = mime_guess::from_path(path)
+            .first_raw()
+            .map(|mime| mime.to_string());
+        // 管道、设备等特殊文件读取时可能一直阻塞，不计算哈希
+        if meta.is_file() {
+            metadata.hash = quick_hash(Path::new(path), meta.len());
+        }
+    }
+    metadata
+}
+
+/**
+ * 计算文件的快速哈希：文件大小加上头部和尾部各 64KB 的 SHA-256，取前 16 字节
+ * 不读取整个文件，大文件也能很快完成，用于查找被移动的文件，不是普通文件时返回 None
+ */
+pub fn quick_hash(path: &Path, size: u64) -> Option<String> {
+    if !fs::metadata(path).ok()?.is_file() {
+        return None;
+    }
+    let mut file = File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    hasher.update(size.to_le_bytes());
+
+    let mut buf = Vec::with_capacity(HASH_CHUNK as usize);
+    (&mut file).take(HASH_CHUNK).read_to_end(&mut buf).ok()?;
+    hasher.update(&buf);
+    if size > HASH_CHUNK * 2 {
+        buf.clear();
+        file.seek(SeekFrom::End(-(HASH_CHUNK as i64))).ok()?;
+        file.take(HASH_CHUNK).read_to_end(&mut buf).ok()?;
+        hasher.update(&buf);
+    } else if size > HASH_CHUNK {
+        buf.clear();
+        file.read_to_end(&mut buf).ok()?;
+        hasher.update(&buf);
+    }
+
+    let digest = hasher.finalize();
+    Some(digest[..16].iter().map(|b| format!("{:02x}", b)).collect())
+}
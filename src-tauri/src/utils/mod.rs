@@ -1 +1,4 @@
-pub mod compress_utils;
\ No newline at end of file
+pub mod compress_utils;
+pub mod file_utils;
+pub mod template_utils;
+pub mod text_utils;
\ No newline at end of file
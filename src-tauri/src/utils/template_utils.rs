@@ -0,0 +1,199 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::Local;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/**
+ * 日期占位符默认格式
+ */
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/**
+ * 时间占位符默认格式
+ */
+const DEFAULT_TIME_FORMAT: &str = "%H:%M:%S";
+
+/**
+ * 片段模板中的占位符
+ */
+#[derive(Clone, Debug, PartialEq)]
+enum Placeholder {
+    /**
+     * {date} 或 {date:格式}，格式同 strftime
+     */
+    Date(String),
+    /**
+     * {time} 或 {time:格式}
+     */
+    Time(String),
+    /**
+     * {clipboard}，当前剪贴板中的文本
+     */
+    Clipboard,
+    /**
+     * {uuid}，随机生成的 UUID
+     */
+    Uuid,
+    /**
+     * {cursor}，粘贴后光标所在位置
+     */
+    Cursor,
+    /**
+     * {prompt:名称}，粘贴时由用户输入
+     */
+    Prompt(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/**
+ * 片段渲染结果
+ */
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedSnippet {
+    pub text: String,
+    /**
+     * {cursor} 所在位置，按字符计算，没有时为空
+     */
+    pub cursor: Option<usize>,
+}
+
+/**
+ * 获取模板中需要用户输入的名称，按首次出现的顺序去重
+ */
+pub fn prompts(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for token in parse(template) {
+        if let Token::Placeholder(Placeholder::Prompt(name)) = token {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/**
+ * 渲染片段模板，clipboard 只在模板包含 {clipboard} 时读取
+ */
+pub fn render(
+    template: &str,
+    values: &HashMap<String, String>,
+    clipboard: impl FnOnce() -> String,
+) -> Result<RenderedSnippet, String> {
+    let mut clipboard = Some(clipboard);
+    let mut clipboard_text: Option<String> = None;
+    let mut text = String::with_capacity(template.len());
+    let mut cursor = None;
+
+    for token in parse(template) {
+        match token {
+            Token::Literal(literal) => text.push_str(&literal),
+            Token::Placeholder(Placeholder::Date(format))
+            | Token::Placeholder(Placeholder::Time(format)) => {
+                text.push_str(&format_now(&format)?);
+            }
+            Token::Placeholder(Placeholder::Clipboard) => {
+                if clipboard_text.is_none() {
+                    clipboard_text = clipboard.take().map(|read| read());
+                }
+                text.push_str(clipboard_text.as_deref().unwrap_or_default());
+            }
+            Token::Placeholder(Placeholder::Uuid) => {
+                text.push_str(&uuid::Uuid::new_v4().to_string());
+            }
+            Token::Placeholder(Placeholder::Cursor) => {
+                // 只有第一个 {cursor} 生效
+                if cursor.is_none() {
+                    cursor = Some(text.chars().count());
+                }
+            }
+            Token::Placeholder(Placeholder::Prompt(name)) => {
+                let value = values
+                    .get(&name)
+                    .ok_or_else(|| format!("缺少输入内容: {}", name))?;
+                text.push_str(value);
+            }
+        }
+    }
+    Ok(RenderedSnippet { text, cursor })
+}
+
+/**
+ * 按格式输出当前时间，格式无效时返回错误而不是 panic
+ */
+fn format_now(format: &str) -> Result<String, String> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(format!("日期格式无效: {}", format));
+    }
+    Ok(Local::now().format_with_items(items.into_iter()).to_string())
+}
+
+/**
+ * 解析模板，{{ 和 }} 表示字面量的花括号，无法识别的占位符原样保留
+ */
+fn parse(template: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        literal.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            literal.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        if rest.starts_with('}') {
+            literal.push('}');
+            rest = &rest[1..];
+            continue;
+        }
+        let placeholder = rest
+            .find('}')
+            .and_then(|end| parse_placeholder(&rest[1..end]).map(|p| (p, end)));
+        match placeholder {
+            Some((placeholder, end)) => {
+                if !literal.is_empty() {
+                    tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+                tokens.push(Token::Placeholder(placeholder));
+                rest = &rest[end + 1..];
+            }
+            None => {
+                literal.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+    tokens
+}
+
+fn parse_placeholder(body: &str) -> Option<Placeholder> {
+    let (name, arg) = match body.split_once(':') {
+        Some((name, arg)) => (name.trim(), Some(arg)),
+        None => (body.trim(), None),
+    };
+    let placeholder = match (name, arg) {
+        ("date", arg) => Placeholder::Date(arg.unwrap_or(DEFAULT_DATE_FORMAT).to_string()),
+        ("time", arg) => Placeholder::Time(arg.unwrap_or(DEFAULT_TIME_FORMAT).to_string()),
+        ("clipboard", None) => Placeholder::Clipboard,
+        ("uuid", None) => Placeholder::Uuid,
+        ("cursor", None) => Placeholder::Cursor,
+        ("prompt", Some(label)) if !label.trim().is_empty() => {
+            Placeholder::Prompt(label.trim().to_string())
+        }
+        _ => return None,
+    };
+    Some(placeholder)
+}
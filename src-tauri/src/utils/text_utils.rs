@@ -0,0 +1,405 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/**
+ * 文本转换方式
+ */
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum TextTransform {
+    UpperCase,
+    LowerCase,
+    TitleCase,
+    CamelCase,
+    SnakeCase,
+    Trim,
+    Dedent,
+    SortLines,
+    UniqueLines,
+    UrlEncode,
+    UrlDecode,
+    Base64Encode,
+    Base64Decode,
+    HtmlEncode,
+    HtmlDecode,
+    JsonPretty,
+    JsonMinify,
+    JsonEscape,
+}
+
+/**
+ * 写入剪贴板前的文本规范化选项
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NormalizeOptions {
+    /**
+     * 合并连续的空格和制表符，并去掉行尾空白
+     */
+    pub collapse_whitespace: bool,
+    /**
+     * 去掉零宽字符
+     */
+    pub remove_zero_width: bool,
+    /**
+     * 把弯引号替换为直引号
+     */
+    pub replace_smart_quotes: bool,
+    /**
+     * 换行符转换为当前系统的格式
+     */
+    pub platform_line_endings: bool,
+}
+
+/**
+ * 按选项规范化文本
+ */
+pub fn normalize(text: &str, options: &NormalizeOptions) -> String {
+    let mut result = text.to_string();
+    if options.remove_zero_width {
+        result.retain(|c| {
+            !matches!(
+                c,
+                '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{2060}' | '\u{feff}'
+            )
+        });
+    }
+    if options.replace_smart_quotes {
+        result = result
+            .chars()
+            .map(|c| match c {
+                '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}' => '\'',
+                '\u{201c}' | '\u{201d}' | '\u{201e}' | '\u{201f}' => '"',
+                _ => c,
+            })
+            .collect();
+    }
+    if options.collapse_whitespace {
+        result = map_lines(&result, collapse_whitespace);
+    }
+    if options.platform_line_endings {
+        let unified = result.replace("\r\n", "\n").replace('\r', "\n");
+        result = if cfg!(windows) {
+            unified.replace('\n', "\r\n")
+        } else {
+            unified
+        };
+    }
+    result
+}
+
+/**
+ * 提取 HTML 中的纯文本
+ */
+pub fn to_plain_text(html: &str) -> String {
+    let trimmed = html.trim();
+
+    let mut result = String::with_capacity(html.len());
+    let mut rest = trimmed;
+    while let Some(start) = rest.find('<') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => break,
+        };
+        let tag = rest[1..end].trim().to_lowercase();
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        rest = &rest[end + 1..];
+
+        // 脚本和样式的内容不属于文本
+        if (name == "script" || name == "style") && !tag.starts_with('/') {
+            let close = format!("</{}", name);
+            rest = match rest.to_ascii_lowercase().find(&close) {
+                Some(pos) => rest[pos..]
+                    .find('>')
+                    .map(|e| &rest[pos + e + 1..])
+                    .unwrap_or(""),
+                None => "",
+            };
+            continue;
+        }
+        let is_block = matches!(
+            name.as_str(),
+            "br" | "p" | "div" | "li" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        );
+        if is_block && (name == "br" || tag.starts_with('/')) {
+            result.push('\n');
+        }
+    }
+    result.push_str(rest);
+    html_decode(result.trim())
+}
+
+/**
+ * 按指定方式转换文本
+ */
+pub fn transform(text: &str, transform: TextTransform) -> Result<String, String> {
+    let result = match transform {
+        TextTransform::UpperCase => text.to_uppercase(),
+        TextTransform::LowerCase => text.to_lowercase(),
+        TextTransform::TitleCase => map_lines(text, title_case),
+        TextTransform::CamelCase => map_lines(text, camel_case),
+        TextTransform::SnakeCase => map_lines(text, snake_case),
+        TextTransform::Trim => text.trim().to_string(),
+        TextTransform::Dedent => dedent(text),
+        TextTransform::SortLines => {
+            let mut lines: Vec<&str> = text.lines().collect();
+            lines.sort();
+            lines.join("\n")
+        }
+        TextTransform::UniqueLines => {
+            let mut seen = HashSet::new();
+            text.lines()
+                .filter(|line| seen.insert(*line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        TextTransform::UrlEncode => url_encode(text),
+        TextTransform::UrlDecode => url_decode(text)?,
+        TextTransform::Base64Encode => base64::engine::general_purpose::STANDARD.encode(text),
+        TextTransform::Base64Decode => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(text.trim())
+                .map_err(|e| format!("Base64 解码失败: {}", e))?;
+            String::from_utf8(bytes).map_err(|_| "Base64 解码结果不是有效的 UTF-8 文本")?
+        }
+        TextTransform::HtmlEncode => html_encode(text),
+        TextTransform::HtmlDecode => html_decode(text),
+        TextTransform::JsonPretty => {
+            let value = parse_json(text)?;
+            serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?
+        }
+        TextTransform::JsonMinify => {
+            let value = parse_json(text)?;
+            serde_json::to_string(&value).map_err(|e| e.to_string())?
+        }
+        TextTransform::JsonEscape => {
+            let quoted = serde_json::to_string(text).map_err(|e| e.to_string())?;
+            quoted[1..quoted.len() - 1].to_string()
+        }
+    };
+    Ok(result)
+}
+
+fn parse_json(text: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(text).map_err(|e| format!("JSON 解析失败: {}", e))
+}
+
+/**
+ * 逐行转换
+ */
+fn map_lines(text: &str, f: fn(&str) -> String) -> String {
+    text.lines().map(f).collect::<Vec<_>>().join("\n")
+}
+
+/**
+ * 拆分单词，支持空格、符号分隔和驼峰命名
+ */
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev: Option<char> = None;
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev = None;
+            continue;
+        }
+        if let Some(p) = prev {
+            if c.is_uppercase() && (p.is_lowercase() || p.is_numeric()) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+        prev = Some(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/**
+ * 首字母大写，其余小写
+ */
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+fn collapse_whitespace(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut in_space = false;
+    for c in line.chars() {
+        if c == ' ' || c == '\t' {
+            if !in_space {
+                result.push(' ');
+            }
+            in_space = true;
+        } else {
+            result.push(c);
+            in_space = false;
+        }
+    }
+    result.trim_end().to_string()
+}
+
+fn title_case(line: &str) -> String {
+    line.split(' ')
+        .map(capitalize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn camel_case(line: &str) -> String {
+    split_words(line)
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            if i == 0 {
+                word.to_lowercase()
+            } else {
+                capitalize(word)
+            }
+        })
+        .collect()
+}
+
+fn snake_case(line: &str) -> String {
+    split_words(line)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/**
+ * 去掉所有非空行共同的缩进
+ */
+fn dedent(text: &str) -> String {
+    let indent = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+    text.lines()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/**
+ * URL 编码，只保留 RFC 3986 中的非保留字符
+ */
+fn url_encode(text: &str) -> String {
+    let mut result = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                result.push(byte as char)
+            }
+            _ => result.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    result
+}
+
+fn url_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = text
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("URL 解码失败，位置 {} 的转义序列无效", i))?;
+            result.push(byte);
+            i += 3;
+        } else {
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(result).map_err(|_| "URL 解码结果不是有效的 UTF-8 文本".to_string())
+}
+
+fn html_encode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/**
+ * HTML 解码，支持常用的命名实体和数字实体，无法识别的实体原样保留
+ */
+fn html_decode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => {
+                    let code = if let Some(hex) =
+                        entity.strip_prefix("#x").or(entity.strip_prefix("#X"))
+                    {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else {
+                        entity
+                            .strip_prefix('#')
+                            .and_then(|dec| dec.parse::<u32>().ok())
+                    };
+                    code.and_then(char::from_u32)
+                }
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
@@ -55,6 +55,7 @@ pub fn build_main_window(app: AppHandle) -> tauri::Result<WebviewWindow> {
             build = build
                 .always_on_top(auto_hide_window || always_on_top)
                 .skip_taskbar(auto_hide_window)
+                .inner_size(width, height)
                 .min_inner_size(width, height);
 
             #[cfg(target_os = "windows")]
@@ -90,4 +91,6 @@ pub fn init_window_position(app: AppHandle) {
         .expect("设置窗口坐标失败");
     win.show().expect("显示窗口失败");
     win.hide().expect("隐藏窗口失败");
+    // 恢复上次关闭时的位置和大小
+    super::geometry::restore_geometry(&app, win);
 }
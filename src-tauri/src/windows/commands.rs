@@ -1,18 +1,6 @@
-use crate::windows::plugin_runner;
 use tauri::AppHandle;
 
 #[tauri::command]
 pub fn init_main_window(app: AppHandle) {
     super::builder::init_window_position(app.clone());
 }
-
-#[tauri::command]
-pub async fn invoke_external_plugin(
-    app: AppHandle,
-    plugin_id: String,
-    plugin_name: String,
-    cmd: String,
-    payload: serde_json::Value,
-) -> Result<String, String> {
-    plugin_runner::run_external_plugin(app, plugin_id, plugin_name, cmd, payload).await
-}
@@ -0,0 +1,211 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{
+    AppHandle, Manager, Monitor, PhysicalPosition, PhysicalRect, PhysicalSize, WebviewWindow,
+    WindowEvent,
+};
+use tauri_plugin_store::StoreExt;
+
+/**
+ * 保存窗口位置和大小的配置文件
+ */
+const WINDOW_STATE_FILE: &str = "windowState.json";
+/**
+ * 隐藏窗口时使用的坐标，处于该坐标时说明窗口还没有显示过
+ */
+const HIDDEN_POSITION: i32 = -1000;
+/**
+ * 窗口停止移动或调整大小后等待多久再保存
+ */
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/**
+ * 每次移动或调整大小时递增，延迟保存时只保存最后一次
+ */
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/**
+ * 窗口在某个屏幕上的位置和大小，单位：物理像素
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/**
+ * 窗口最后一次的位置和大小，以及所在的屏幕
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct LastGeometry {
+    monitor: String,
+    #[serde(flatten)]
+    geometry: WindowGeometry,
+}
+
+/**
+ * 监听窗口移动和调整大小，停止变化后保存位置和大小
+ */
+pub fn watch_geometry(app: &AppHandle, win: &WebviewWindow) {
+    let app = app.clone();
+    let label = win.label().to_string();
+    win.on_window_event(move |event| {
+        if !matches!(event, WindowEvent::Moved(_) | WindowEvent::Resized(_)) {
+            return;
+        }
+        let generation = SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        let app = app.clone();
+        let label = label.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SAVE_DELAY).await;
+            if SAVE_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            if let Some(win) = app.get_webview_window(&label) {
+                // 隐藏时的移动来自程序调整位置，不需要保存
+                if win.is_visible().unwrap_or(false) {
+                    remember_geometry(&app, &win);
+                }
+            }
+        });
+    });
+}
+
+/**
+ * 恢复窗口上次的位置和大小，上次所在的屏幕已断开时在主屏幕中间显示
+ */
+pub fn restore_geometry(app: &AppHandle, win: &WebviewWindow) {
+    let last = match load_last_geometry(app) {
+        Some(last) => last,
+        None => return,
+    };
+    let monitors = app.available_monitors().unwrap_or_default();
+    let saved_monitor = monitors
+        .into_iter()
+        .find(|monitor| monitor_key(monitor) == last.monitor);
+    let found = saved_monitor.is_some();
+    let monitor = match saved_monitor.or_else(|| app.primary_monitor().ok().flatten()) {
+        Some(monitor) => monitor,
+        None => return,
+    };
+    if !found {
+        info!("窗口上次所在的屏幕 {} 已断开，改为在主屏幕显示", last.monitor);
+    }
+
+    let work_area = monitor.work_area();
+    let size = PhysicalSize::new(last.geometry.width, last.geometry.height);
+    let position = if found {
+        PhysicalPosition::new(last.geometry.x, last.geometry.y)
+    } else {
+        center_of(work_area, size)
+    };
+    let (position, size) = clamp_to(work_area, position, size);
+    if let Err(e) = win.set_size(size) {
+        warn!("恢复窗口大小失败: {}", e);
+    }
+    if let Err(e) = win.set_position(position) {
+        warn!("恢复窗口位置失败: {}", e);
+    }
+}
+
+pub fn center_of(area: &PhysicalRect<i32, u32>, size: PhysicalSize<u32>) -> PhysicalPosition<i32> {
+    PhysicalPosition::new(
+        area.position.x + (area.size.width as i32 - size.width as i32) / 2,
+        area.position.y + (area.size.height as i32 - size.height as i32) / 2,
+    )
+}
+
+/**
+ * 把窗口限制在屏幕工作区内，窗口比工作区大时缩小到工作区大小
+ */
+pub fn clamp_to(
+    area: &PhysicalRect<i32, u32>,
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+    let size = PhysicalSize::new(
+        size.width.min(area.size.width),
+        size.height.min(area.size.height),
+    );
+    let right = area.position.x + (area.size.width - size.width) as i32;
+    let bottom = area.position.y + (area.size.height - size.height) as i32;
+    let position = PhysicalPosition::new(
+        position.x.clamp(area.position.x, right),
+        position.y.clamp(area.position.y, bottom),
+    );
+    (position, size)
+}
+
+/**
+ * 屏幕的标识，没有名称时使用屏幕坐标
+ */
+fn monitor_key(monitor: &Monitor) -> String {
+    match monitor.name() {
+        Some(name) => name.clone(),
+        None => format!("{},{}", monitor.position().x, monitor.position().y),
+    }
+}
+
+/**
+ * 读取窗口在指定屏幕上保存的位置和大小
+ */
+pub fn load_geometry(app: &AppHandle, monitor: &Monitor) -> Option<WindowGeometry> {
+    let store = app.store(WINDOW_STATE_FILE).ok()?;
+    let monitors = store.get("monitors")?;
+    serde_json::from_value(monitors.get(monitor_key(monitor))?.clone()).ok()
+}
+
+fn load_last_geometry(app: &AppHandle) -> Option<LastGeometry> {
+    let store = app.store(WINDOW_STATE_FILE).ok()?;
+    serde_json::from_value(store.get("last")?).ok()
+}
+
+/**
+ * 保存窗口在当前所在屏幕上的位置和大小
+ */
+pub fn remember_geometry(app: &AppHandle, win: &WebviewWindow) {
+    let (position, size) = match (win.outer_position(), win.outer_size()) {
+        (Ok(position), Ok(size)) => (position, size),
+        _ => return,
+    };
+    if position.x == HIDDEN_POSITION && position.y == HIDDEN_POSITION {
+        return;
+    }
+    let monitor = match win.current_monitor() {
+        Ok(Some(monitor)) => monitor,
+        _ => return,
+    };
+    let store = match app.store(WINDOW_STATE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("打开窗口位置配置失败: {}", e);
+            return;
+        }
+    };
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+    let last = LastGeometry {
+        monitor: monitor_key(&monitor),
+        geometry,
+    };
+
+    let mut monitors = store
+        .get("monitors")
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_else(Map::new);
+    monitors.insert(
+        last.monitor.clone(),
+        serde_json::to_value(geometry).unwrap_or(Value::Null),
+    );
+    store.set("monitors", Value::Object(monitors));
+    store.set("last", serde_json::to_value(last).unwrap_or(Value::Null));
+}
@@ -1,13 +1,19 @@
 mod builder;
-mod plugin_runner;
 mod commands;
+mod geometry;
+mod placement;
 
 use tauri::{AppHandle, Manager};
 use crate::windows::builder::build_main_window;
 
 pub use commands::*;
+pub use placement::place_list_window;
 
 pub fn create_main_window(app: AppHandle) {
     let window =  build_main_window(app.clone());
+    if let Ok(win) = &window {
+        // 移动或调整大小后保存窗口位置
+        geometry::watch_geometry(&app, win);
+    }
     app.manage(window);
 }
@@ -0,0 +1,162 @@
+use crate::tray::state::TRAY_STATE;
+use crate::windows::geometry::{center_of, clamp_to, load_geometry, remember_geometry};
+use log::warn;
+use serde::Deserialize;
+use tauri::{
+    AppHandle, LogicalPosition, Monitor, PhysicalPosition, PhysicalRect, PhysicalSize, Position,
+    WebviewWindow,
+};
+use tauri_plugin_store::StoreExt;
+
+/**
+ * 列表窗口的打开位置
+ */
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PlacementMode {
+    /**
+     * 鼠标位置
+     */
+    #[default]
+    Cursor,
+    /**
+     * 鼠标所在屏幕的中间
+     */
+    MonitorCenter,
+    /**
+     * 托盘图标附近
+     */
+    Tray,
+    /**
+     * 上次在该屏幕关闭时的位置
+     */
+    LastPosition,
+}
+
+/**
+ * 按配置的打开位置移动列表窗口，需要在显示窗口前调用
+ */
+pub fn place_list_window(app: &AppHandle, win: &WebviewWindow) {
+    // 记录窗口在当前屏幕上的位置，用于下次在该屏幕打开
+    remember_geometry(app, win);
+
+    let mode = placement_mode(app);
+    let anchor = anchor_point(app, mode);
+    let monitor = match monitor_at(app, anchor) {
+        Some(monitor) => monitor,
+        None => {
+            warn!("找不到窗口所在的屏幕，保持原位置");
+            return;
+        }
+    };
+    let work_area = monitor.work_area();
+    let saved = load_geometry(app, &monitor);
+
+    let size = match saved {
+        Some(geometry) => PhysicalSize::new(geometry.width, geometry.height),
+        None => win.outer_size().unwrap_or(PhysicalSize::new(350, 550)),
+    };
+    let position = match mode {
+        PlacementMode::Tray => tray_position(app, size, work_area),
+        PlacementMode::LastPosition => saved.map(|g| PhysicalPosition::new(g.x, g.y)),
+        PlacementMode::Cursor => anchor,
+        PlacementMode::MonitorCenter => None,
+    }
+    .unwrap_or_else(|| center_of(work_area, size));
+
+    let (position, size) = clamp_to(work_area, position, size);
+    if saved.is_some() {
+        if let Err(e) = win.set_size(size) {
+            warn!("设置窗口大小失败: {}", e);
+        }
+    }
+    if let Err(e) = win.set_position(position) {
+        warn!("设置窗口位置失败: {}", e);
+    }
+}
+
+fn placement_mode(app: &AppHandle) -> PlacementMode {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("windowPlacement"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/**
+ * 窗口打开位置的参考点，用于确定窗口所在的屏幕
+ */
+fn anchor_point(app: &AppHandle, mode: PlacementMode) -> Option<PhysicalPosition<i32>> {
+    if mode == PlacementMode::Tray {
+        if let Some(rect) = tray_rect(app) {
+            return Some(rect.position);
+        }
+    }
+    app.cursor_position()
+        .ok()
+        .map(|p| PhysicalPosition::new(p.x as i32, p.y as i32))
+}
+
+/**
+ * 参考点所在的屏幕，找不到时使用主屏幕
+ */
+fn monitor_at(app: &AppHandle, point: Option<PhysicalPosition<i32>>) -> Option<Monitor> {
+    point
+        .and_then(|p| app.monitor_from_point(p.x as f64, p.y as f64).ok().flatten())
+        .or_else(|| app.primary_monitor().ok().flatten())
+}
+
+fn tray_rect(app: &AppHandle) -> Option<PhysicalRect<i32, u32>> {
+    let rect = {
+        let state = TRAY_STATE.lock().unwrap();
+        state.tray.as_ref()?.rect().ok()??
+    };
+    let scale = match rect.position {
+        Position::Physical(_) => 1.0,
+        Position::Logical(position) => tray_scale_factor(app, position),
+    };
+    Some(PhysicalRect {
+        position: rect.position.to_physical(scale),
+        size: rect.size.to_physical(scale),
+    })
+}
+
+/**
+ * 托盘图标所在屏幕的缩放比例，托盘区域是逻辑坐标时按该比例换算为物理坐标
+ */
+fn tray_scale_factor(app: &AppHandle, position: LogicalPosition<f64>) -> f64 {
+    let monitors = app.available_monitors().unwrap_or_default();
+    monitors
+        .into_iter()
+        .find(|monitor| {
+            let point = position.to_physical::<i32>(monitor.scale_factor());
+            let origin = monitor.position();
+            let size = monitor.size();
+            point.x >= origin.x
+                && point.y >= origin.y
+                && point.x < origin.x + size.width as i32
+                && point.y < origin.y + size.height as i32
+        })
+        .or_else(|| app.primary_monitor().ok().flatten())
+        .map(|monitor| monitor.scale_factor())
+        .unwrap_or(1.0)
+}
+
+/**
+ * 托盘图标附近的位置，托盘在屏幕下半部分时窗口显示在托盘上方，否则显示在下方
+ */
+fn tray_position(
+    app: &AppHandle,
+    size: PhysicalSize<u32>,
+    work_area: &PhysicalRect<i32, u32>,
+) -> Option<PhysicalPosition<i32>> {
+    let tray = tray_rect(app)?;
+    let x = tray.position.x + tray.size.width as i32 / 2 - size.width as i32 / 2;
+    let middle = work_area.position.y + work_area.size.height as i32 / 2;
+    let y = if tray.position.y > middle {
+        tray.position.y - size.height as i32
+    } else {
+        tray.position.y + tray.size.height as i32
+    };
+    Some(PhysicalPosition::new(x, y))
+}
@@ -16,6 +16,7 @@ export const SETTINGS_KEYS = {
   WINDOW_WIDTH: 'windowWidth',
   WINDOW_HEIGHT: 'windowHeight',
   AUTO_HIDE_WINDOW: 'autoHideWindow',
+  WINDOW_PLACEMENT: 'windowPlacement',
   ALWAYS_ON_TOP: 'alwaysOnTop',
   NEW_VERSION_ALERT_MODE: 'newVersionAlertMode',
   ENABLE_ANIMATION_EFFECTS: 'enableAnimationEffects',
@@ -27,6 +28,10 @@ export const SETTINGS_KEYS = {
   IMAGE_BASE_PATH: 'imageBasePath',
   ENABLE_IMAGE_SAVE: 'enableImageSave',
   PLUGIN_PATH: 'pluginPath',
+  PLUGIN_TRUSTED_KEYS: 'pluginTrustedKeys',
+  CAPTURE_PRIMARY_SELECTION: 'capturePrimarySelection',
+  SYNC_SELECTIONS: 'syncSelections',
+  PLAIN_TEXT_NORMALIZE: 'plainTextNormalize',
   DISPLAY_DETAIL_TIME: 'displayDetailTime',
   BACKGROUND_ANIMATION_EFFECT: 'backgroundAnimationEffect',
   SEARCH_MODEL: 'searchModel',
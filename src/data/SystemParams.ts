@@ -19,3 +19,8 @@ export const isDev = env === 'development'
  * 判断当前是否为Mac操作系统
  */
 export const isMac = currentPlatform === 'macos'
+
+/**
+ * 判断当前是否为Linux操作系统
+ */
+export const isLinux = currentPlatform === 'linux'
@@ -1,12 +1,19 @@
 import { reactive, ref } from 'vue'
 import { selectedTagState } from './TagDataComposable.ts'
 import ClipboardDBService from '../../../services/ClipboardDBService.ts'
-import { error } from '@tauri-apps/plugin-log'
-import { exists } from '@tauri-apps/plugin-fs'
+import { error, info } from '@tauri-apps/plugin-log'
 import {
+  ClipboardWriteError,
+  copyAsPlainText,
   copyFileToClipboard,
   copyToClipboard,
+  enqueuePaste,
+  FileItemValidation,
   initClipboardListener,
+  pasteToTarget,
+  setTrackedFileItems,
+  toWriteRequest,
+  untrackFileItem,
 } from '../../../services/ClipboardService.ts'
 import { hideWindow, isAutoHideWindow } from './WindowComposable.ts'
 import {
@@ -16,10 +23,12 @@ import {
   removeCache,
 } from './FileDataComposable.ts'
 import { emit, listen, UnlistenFn } from '@tauri-apps/api/event'
+import { invoke } from '@tauri-apps/api/core'
 import DataClearService from '../../../services/DataClearService.ts'
 import {
   getAutoGoToLatestData,
   getDataRetentionDays,
+  getPlainTextNormalize,
   getSearchModel,
 } from '../../../store/Settings.ts'
 import { scrollToDiv } from '../../../utils/DomUtil.ts'
@@ -32,6 +41,9 @@ import { openSearchWindow } from '../../../services/WindowService.ts'
 // 剪贴板监听
 let clipboardListener: any = null
 
+// 粘贴队列中显示的摘要长度
+const QUEUE_LABEL_LENGTH = 30
+
 // 内容列表
 export const clipboardItems = ref<ClipboardItem[]>([])
 
@@ -404,6 +416,7 @@ export const removeItem = async (
   clipboardItems.value.splice(index, 1)
   // 删除文件、文件夹是否存在缓存
   if (item.type === 'file') {
+    await untrackFileItem(item.id)
     if (item.file_path.startsWith('[')) {
       JSON.parse(item.file_path).forEach((path: string) => {
         removeCache(path)
@@ -433,31 +446,107 @@ export const onCopy = async (item: ClipboardItem, message: MessageApiInjection):
       return
     }
     if (item) {
-      let isSuccess
-      if (item.type === 'file') {
-        const filePaths: Array<string> = JSON.parse(item.file_path)
-        // 过滤出系统存在的文件
-        const paths = filePaths.filter(async path => {
-          return fileExistCache.value.get(path) && (await exists(path))
-        })
-        item.file_path = JSON.stringify(paths)
-        isSuccess = await copyToClipboard(item)
-      } else {
-        isSuccess = await copyToClipboard(item)
-      }
-      if (isSuccess) {
+      try {
+        // 文件不存在时由后端返回 fileNotFound，不写入失效的路径
+        await copyToClipboard(item)
         message.success(currentLanguage.value.pages.list.copySuccessMsg)
         // 隐藏窗口
         if (isAutoHideWindow.value) {
           await hideWindow()
         }
+      } catch (e) {
+        showCopyError(e as ClipboardWriteError, message)
+      }
+    }
+  }, 10)
+}
+
+/**
+ * 粘贴到打开列表窗口前的窗口，没有目标窗口或当前平台不支持粘贴时只写入剪贴板
+ * @param item 剪贴板内容
+ * @param message 消息提示
+ */
+export const onPaste = async (item: ClipboardItem, message: MessageApiInjection): Promise<void> => {
+  setTimeout(async () => {
+    if (selectedItemId.value === item.id) {
+      return
+    }
+    try {
+      await pasteToTarget(toWriteRequest(item))
+    } catch (e: any) {
+      if (e?.kind === 'noTarget' || e?.kind === 'unsupported') {
+        // 写入剪贴板由用户手动粘贴
+        await onCopy(item, message)
+      } else if (e?.kind === 'clipboard') {
+        showCopyError(e.error, message)
       } else {
-        message.error(currentLanguage.value.pages.list.copyFailedMsg)
+        error('粘贴失败: ' + JSON.stringify(e))
+        message.error(currentLanguage.value.pages.list.pasteFailedMsg)
       }
     }
   }, 10)
 }
 
+/**
+ * 复制为纯文本，按设置中勾选的选项规范化文本
+ * @param item 剪贴板内容
+ * @param message 消息提示
+ */
+export const onCopyAsPlainText = async (
+  item: ClipboardItem,
+  message: MessageApiInjection
+): Promise<void> => {
+  try {
+    const options = await getPlainTextNormalize()
+    await copyAsPlainText(item, Object.fromEntries(options.map(option => [option, true])))
+    message.success(currentLanguage.value.pages.list.copySuccessMsg)
+    if (isAutoHideWindow.value) {
+      await hideWindow()
+    }
+  } catch (e) {
+    showCopyError(e as ClipboardWriteError, message)
+  }
+}
+
+/**
+ * 加入粘贴队列，之后用粘贴队列快捷键依次粘贴
+ * @param item 剪贴板内容
+ * @param message 消息提示
+ */
+export const onEnqueuePaste = async (
+  item: ClipboardItem,
+  message: MessageApiInjection
+): Promise<void> => {
+  const label = item.content.trim().split('\n')[0].slice(0, QUEUE_LABEL_LENGTH) || item.type
+  try {
+    // 同一条记录可以多次加入队列，id 需要唯一
+    await enqueuePaste({ id: `${item.id}-${Date.now()}`, label, request: toWriteRequest(item) })
+    message.success(currentLanguage.value.pages.list.enqueuePasteSuccessMsg)
+  } catch (e) {
+    error('加入粘贴队列失败: ' + JSON.stringify(e))
+    message.error(currentLanguage.value.pages.list.enqueuePasteFailedMsg)
+  }
+}
+
+/**
+ * 显示复制失败的原因
+ * @param e 写入剪贴板失败的原因
+ * @param message 消息提示
+ */
+const showCopyError = (e: ClipboardWriteError, message: any): void => {
+  error('复制到剪贴板失败: ' + JSON.stringify(e))
+  if (e.kind === 'fileNotFound' && e.path) {
+    fileExistCache.value.set(e.path, false)
+    message.error(
+      currentLanguage.value.pages.list.fileNotExistPathCopyFailedMsg.replace('{path}', e.path)
+    )
+  } else if (e.kind === 'fileNotFound') {
+    message.error(currentLanguage.value.pages.list.fileNotExistCopyFailedMsg)
+  } else {
+    message.error(currentLanguage.value.pages.list.copyFailedMsg)
+  }
+}
+
 /**
  * 将单个文件复制到系统剪贴板
  * @param itemId 项目ID
@@ -473,19 +562,15 @@ export const onCopyFile = async (
 ): Promise<void> => {
   if (filePath) {
     selectedItemId.value = itemId
-    if (fileExistCache.value.get(filePath) && (await exists(filePath))) {
-      const isSuccess = await copyFileToClipboard([filePath])
-      if (isSuccess) {
-        message.success(language.pages.list.copySuccessMsg)
-        // 隐藏窗口
-        if (isAutoHideWindow.value) {
-          await hideWindow()
-        }
-      } else {
-        message.error(language.pages.list.copyFailedMsg)
+    try {
+      await copyFileToClipboard([filePath])
+      message.success(language.pages.list.copySuccessMsg)
+      // 隐藏窗口
+      if (isAutoHideWindow.value) {
+        await hideWindow()
       }
-    } else {
-      message.error(language.pages.list.fileNotExistCopyFailedMsg)
+    } catch (e) {
+      showCopyError(e as ClipboardWriteError, message)
     }
   }
   setTimeout(() => {
@@ -684,6 +769,72 @@ async function initDisplayDetailTimeListener(): Promise<UnlistenFn> {
   })
 }
 
+/**
+ * 初始化文件记录检查结果监听，保存被移动文件的新位置和文件是否丢失
+ */
+let fileValidationListener: any = null
+
+async function initFileValidationListener(): Promise<UnlistenFn> {
+  return await listen('file-items-validated', async (event: any) => {
+    const changes: FileItemValidation[] = event.payload
+    const db = await ClipboardDBService.getInstance()
+    for (const change of changes) {
+      const filePath = JSON.stringify(change.paths)
+      const fileMeta = JSON.stringify(change.meta)
+      const missing = change.missing.length > 0
+      await db.updateItemFileState(change.id, filePath, fileMeta, missing)
+      change.relocated.forEach(relocation => {
+        info(`文件已被移动: ${relocation.from} -> ${relocation.to}`)
+        removeCache(relocation.from)
+      })
+      change.paths.forEach(path => {
+        fileExistCache.value.set(path, !change.missing.includes(path))
+      })
+      const item = clipboardItems.value.find(item => item.id === change.id)
+      if (item) {
+        item.file_path = filePath
+        item.file_meta = fileMeta
+        item.file_missing = missing ? 1 : 0
+      }
+    }
+  })
+}
+
+/**
+ * 初始化插件读取剪贴板历史的请求监听，查询后返回给后端
+ */
+let pluginHistoryListener: any = null
+
+async function initPluginHistoryListener(): Promise<UnlistenFn> {
+  return await listen('plugin-history-request', async (event: any) => {
+    const { requestId, limit } = event.payload
+    const db = await ClipboardDBService.getInstance()
+    const items = await db.getRecentItems(limit).catch(e => {
+      error('插件读取剪贴板历史失败:' + e)
+      return []
+    })
+    await invoke('resolve_plugin_history', {
+      requestId,
+      items: items.map(item => ({
+        id: item.id,
+        type: item.type,
+        content: item.content,
+        filePath: item.file_path,
+        copyTime: item.copy_time,
+      })),
+    })
+  })
+}
+
+/**
+ * 将所有文件记录交给后台检查
+ */
+async function trackAllFileItems(): Promise<void> {
+  const db = await ClipboardDBService.getInstance()
+  const items = await db.getItems('file')
+  await setTrackedFileItems(items || [])
+}
+
 /**
  * 初始化剪贴板数据配置
  */
@@ -722,6 +873,15 @@ export async function initializeClipboardData(): Promise<void> {
 
     // 添加搜索更新事件监听
     searchUpdateListener = await initSearchUpdateListener()
+
+    // 添加文件记录检查结果监听，并开始后台检查
+    fileValidationListener = await initFileValidationListener()
+    trackAllFileItems().catch(e => {
+      error('文件记录后台检查启动失败:' + e)
+    })
+
+    // 添加插件读取剪贴板历史的请求监听
+    pluginHistoryListener = await initPluginHistoryListener()
   } catch (e) {
     console.error('初始化剪贴板数据配置失败:', e)
     await error('初始化剪贴板数据配置失败:' + e)
@@ -770,4 +930,14 @@ export async function destroyClipboardData(): Promise<void> {
   if (searchUpdateListener) {
     searchUpdateListener()
   }
+
+  // 销毁监听文件记录检查结果事件
+  if (fileValidationListener) {
+    fileValidationListener()
+  }
+
+  // 销毁监听插件读取剪贴板历史的请求
+  if (pluginHistoryListener) {
+    pluginHistoryListener()
+  }
 }
@@ -5,6 +5,8 @@ import { initShortcutKeys } from '../../../store/ShortcutKeys.ts'
 import { filePathConvertFileName } from '../../../utils/TextUtil.ts'
 import { listen, UnlistenFn } from '@tauri-apps/api/event'
 import { info } from '@tauri-apps/plugin-log'
+import { convertFileSize } from '../../../utils/FileUtil.ts'
+import { currentLanguage } from '../../../services/LanguageService.ts'
 
 /**
  * 文件是否存在缓存，用于存储文件是否存在状态
@@ -15,6 +17,11 @@ export const fileExistCache = ref(new Map<string, boolean>())
  */
 export const isFolderCache = ref(new Map<string, boolean>())
 
+/**
+ * 文件元数据解析缓存，key 为记录中保存的 JSON
+ */
+const fileMetaCache = new Map<string, Map<string, FileMetadata>>()
+
 /**
  * 显示缩略图图片
  */
@@ -93,6 +100,66 @@ async function loadFileIsFolder(filePath: string): Promise<void> {
   }
 }
 
+/**
+ * 获取记录中保存的文件元数据
+ * @param fileMeta 记录中的文件元数据（JSON）
+ * @param filePath 文件路径
+ */
+export const getFileMeta = (
+  fileMeta: string | null,
+  filePath: string
+): FileMetadata | undefined => {
+  if (!fileMeta) {
+    return undefined
+  }
+  let metaMap = fileMetaCache.get(fileMeta)
+  if (!metaMap) {
+    try {
+      const list: FileMetadata[] = JSON.parse(fileMeta)
+      metaMap = new Map(list.map(meta => [meta.path, meta]))
+    } catch (e) {
+      console.error('解析文件元数据失败:', e)
+      metaMap = new Map()
+    }
+    fileMetaCache.set(fileMeta, metaMap)
+  }
+  return metaMap.get(filePath)
+}
+
+/**
+ * 判断文件是否为文件夹，没有检查结果时使用复制时采集的元数据
+ * @param fileMeta 记录中的文件元数据（JSON）
+ * @param filePath 文件路径
+ */
+export const isFolderPath = (fileMeta: string | null, filePath: string): boolean | undefined => {
+  return isFolderCache.value.get(filePath) ?? getFileMeta(fileMeta, filePath)?.isDir
+}
+
+/**
+ * 文件的悬浮提示，包含路径、大小（文件夹为包含的数量）和修改时间
+ * @param fileMeta 记录中的文件元数据（JSON）
+ * @param filePath 文件路径
+ */
+export const getFileTitle = (fileMeta: string | null, filePath: string): string => {
+  const meta = getFileMeta(fileMeta, filePath)
+  if (!meta || !meta.exists) {
+    return filePath
+  }
+  const language = currentLanguage.value.pages.list
+  const lines = [filePath]
+  if (meta.isDir) {
+    if (meta.itemCount !== null) {
+      lines.push(language.folderItemCount.replace('{count}', String(meta.itemCount)))
+    }
+  } else {
+    lines.push(`${language.fileSize}: ${convertFileSize(meta.size)}`)
+  }
+  if (meta.modified) {
+    lines.push(`${language.fileModified}: ${new Date(meta.modified).toLocaleString()}`)
+  }
+  return lines.join('\n')
+}
+
 /**
  * 移除缓存
  * @param filePath 文件路径
@@ -22,6 +22,22 @@ async function initPlugins(): Promise<void> {
   }
 }
 
+/**
+ * 解析插件返回的结果，旧插件返回的是 JSON 字符串
+ * @param result 插件返回的结果
+ */
+function parsePluginResult(result: any): any {
+  if (typeof result === 'string') {
+    try {
+      return JSON.parse(result)
+    } catch (e) {
+      error('插件返回的结果不是 JSON: ' + result)
+      return {}
+    }
+  }
+  return result ?? {}
+}
+
 /**
  * 注册右键菜单
  * @param pluginId 插件ID
@@ -77,8 +93,8 @@ function registerContextMenu(
 
         console.log('插件调用结果:', result)
         const response = JSON.parse(result)
-        // 插件返回的是嵌套结构: { result: '{"action": "...", ...}' }
-        const actionResult = response.result ? JSON.parse(response.result) : {}
+        // 插件返回的是嵌套结构: { result: {"action": "...", ...} }
+        const actionResult = parsePluginResult(response.result)
 
         console.log('插件 action 结果:', actionResult)
 
@@ -110,7 +126,7 @@ function registerContextMenu(
         // 其他情况插件自行处理，不打开窗口
       } catch (e) {
         console.error('插件调用失败:', e)
-        error('插件调用失败: ' + e)
+        error('插件调用失败: ' + JSON.stringify(e))
       }
     }
   }
@@ -0,0 +1,161 @@
+import { reactive, ref } from 'vue'
+import { error } from '@tauri-apps/plugin-log'
+import type { MessageApiInjection } from 'naive-ui/es/message/src/MessageProvider'
+import ClipboardDBService from '../../../services/ClipboardDBService.ts'
+import {
+  getSnippetPrompts,
+  pasteToTarget,
+  renderSnippet,
+  writeClipboard,
+} from '../../../services/ClipboardService.ts'
+import { currentLanguage } from '../../../services/LanguageService.ts'
+import { hideWindow, isAutoHideWindow } from './WindowComposable.ts'
+
+// 片段名称的最大长度
+const SNIPPET_NAME_LENGTH = 20
+
+// 片段列表
+export const snippets = ref<SnippetItem[]>([])
+
+// 是否显示片段列表
+export const showSnippetModal = ref(false)
+
+// 需要用户输入的占位符
+export const promptState = reactive({
+  snippet: null as SnippetItem | null,
+  names: [] as string[],
+  values: {} as Record<string, string>,
+})
+
+/**
+ * 打开片段列表
+ */
+export const openSnippetModal = async (): Promise<void> => {
+  showSnippetModal.value = true
+  cancelPrompts()
+  await loadSnippets()
+}
+
+/**
+ * 加载所有片段
+ */
+export const loadSnippets = async (): Promise<void> => {
+  try {
+    const db = await ClipboardDBService.getInstance()
+    snippets.value = await db.getAllSnippets()
+  } catch (e) {
+    error('加载片段失败: ' + e)
+  }
+}
+
+/**
+ * 将文本内容保存为片段，名称取内容的第一行
+ * @param item 剪贴板内容
+ * @param message 消息提示
+ */
+export const saveAsSnippet = async (
+  item: ClipboardItem,
+  message: MessageApiInjection
+): Promise<void> => {
+  const name = item.content.trim().split('\n')[0].slice(0, SNIPPET_NAME_LENGTH)
+  try {
+    const db = await ClipboardDBService.getInstance()
+    await db.addSnippet(name || currentLanguage.value.pages.list.snippet.untitled, item.content)
+    message.success(currentLanguage.value.pages.list.snippet.saveSuccessMsg)
+  } catch (e) {
+    error('保存片段失败: ' + e)
+    message.error(currentLanguage.value.pages.list.snippet.saveFailedMsg)
+  }
+}
+
+/**
+ * 删除片段
+ * @param snippet 片段
+ * @param message 消息提示
+ */
+export const removeSnippet = async (
+  snippet: SnippetItem,
+  message: MessageApiInjection
+): Promise<void> => {
+  const db = await ClipboardDBService.getInstance()
+  await db.deleteSnippet(snippet.id)
+  snippets.value = snippets.value.filter(s => s.id !== snippet.id)
+  message.success(currentLanguage.value.pages.list.deleteSuccessMsg)
+}
+
+/**
+ * 选择片段，有 {prompt:名称} 占位符时先让用户输入，否则直接粘贴
+ * @param snippet 片段
+ * @param message 消息提示
+ */
+export const onSelectSnippet = async (
+  snippet: SnippetItem,
+  message: MessageApiInjection
+): Promise<void> => {
+  const names = await getSnippetPrompts(snippet.content)
+  if (names.length > 0) {
+    promptState.snippet = snippet
+    promptState.names = names
+    promptState.values = Object.fromEntries(names.map(name => [name, '']))
+    return
+  }
+  await pasteSnippet(snippet, {}, message)
+}
+
+/**
+ * 用户输入完成后粘贴片段
+ * @param message 消息提示
+ */
+export const confirmPrompts = async (message: MessageApiInjection): Promise<void> => {
+  if (promptState.snippet) {
+    const snippet = promptState.snippet
+    const values = { ...promptState.values }
+    cancelPrompts()
+    await pasteSnippet(snippet, values, message)
+  }
+}
+
+/**
+ * 取消输入
+ */
+export const cancelPrompts = (): void => {
+  promptState.snippet = null
+  promptState.names = []
+  promptState.values = {}
+}
+
+/**
+ * 渲染片段并粘贴到打开列表窗口前的窗口，有 {cursor} 时把光标移动到该位置，无法粘贴时只写入剪贴板
+ * @param snippet 片段
+ * @param values 用户输入的内容
+ * @param message 消息提示
+ */
+const pasteSnippet = async (
+  snippet: SnippetItem,
+  values: Record<string, string>,
+  message: MessageApiInjection
+): Promise<void> => {
+  try {
+    const rendered = await renderSnippet(snippet.content, values)
+    showSnippetModal.value = false
+    try {
+      // cursor 按字符计算，需要向左移动的字符数为其后的字符数
+      const caretLeft =
+        rendered.cursor !== null ? [...rendered.text].length - rendered.cursor : undefined
+      await pasteToTarget({ format: 'text', text: rendered.text }, caretLeft)
+    } catch (e: any) {
+      // 没有目标窗口或当前平台不支持粘贴时，写入剪贴板由用户手动粘贴
+      if (e?.kind !== 'noTarget' && e?.kind !== 'unsupported') {
+        throw e
+      }
+      await writeClipboard({ format: 'text', text: rendered.text })
+      message.success(currentLanguage.value.pages.list.copySuccessMsg)
+      if (isAutoHideWindow.value) {
+        await hideWindow()
+      }
+    }
+  } catch (e) {
+    error('粘贴片段失败: ' + JSON.stringify(e))
+    message.error(currentLanguage.value.pages.list.snippet.pasteFailedMsg)
+  }
+}
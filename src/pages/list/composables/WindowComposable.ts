@@ -1,6 +1,6 @@
 import {
   clipboardItems,
-  onCopy,
+  onPaste,
   showSearchBox,
   toggleSearchBox,
 } from './ClipboardDataComposable.ts'
@@ -9,23 +9,16 @@ import { listFixedStore } from '../../../store/Fixed.ts'
 import { listen, UnlistenFn } from '@tauri-apps/api/event'
 import { openAboutWindow, openSettingsWindow } from '../../../services/WindowService.ts'
 import UpdaterService from '../../../services/UpdaterService.ts'
-import { convertRegisterKeys, formatKeysDisplay } from '../../../utils/ShortcutKeysUtil.ts'
-import { isRegistered, register, unregister } from '@tauri-apps/plugin-global-shortcut'
+import { formatKeysDisplay } from '../../../utils/ShortcutKeysUtil.ts'
 import {
   saveQuickPasteKeyAvailable,
   saveWakeUpRoutineKeyAvailable,
 } from '../../../store/ShortcutKeyAvailableStatus.ts'
 import { error, info } from '@tauri-apps/plugin-log'
 import { sendNotification } from '@tauri-apps/plugin-notification'
-import {
-  currentMonitor,
-  cursorPosition,
-  getCurrentWindow,
-  PhysicalPosition,
-} from '@tauri-apps/api/window'
+import { getCurrentWindow } from '@tauri-apps/api/window'
 import { ref } from 'vue'
 import { getAutoHideWindow } from '../../../store/Settings.ts'
-import { getQuickPaste, getWakeUpRoutine } from '../../../store/ShortcutKeys.ts'
 import { invoke } from '@tauri-apps/api/core'
 import type { MessageApiInjection } from 'naive-ui/es/message/src/MessageProvider'
 import { currentLanguage } from '../../../services/LanguageService.ts'
@@ -46,6 +39,9 @@ export const textContextMenus = ref<ContextMenu[]>([])
 // 是否在监听剪贴板数据
 export const isListening = ref(true)
 
+// 剪贴板监听状态，暂停时包含自动恢复的时间
+export const listeningStatus = ref<ListeningStatus>({ status: 'listening' })
+
 /**
  * 隐藏当前窗口
  */
@@ -63,143 +59,40 @@ export async function hideWindow(): Promise<void> {
 }
 
 /**
- * 注册打开当前窗口快捷键
- * @param shortcutKeys 快捷键
+ * 保存快捷键可用状态，启动时对注册失败的快捷键发送通知
+ * @param statuses 快捷键注册结果
+ * @param notify 是否发送通知
  */
-async function registerOpenWindowKey(shortcutKeys: string): Promise<void> {
-  console.debug('注册打开当前窗口快捷键: ' + shortcutKeys)
-  await register(shortcutKeys, async event => {
-    // 只监听按键按下，松开忽略
-    if (event.state === 'Released') {
-      return
-    }
-    const webWin = getCurrentWebviewWindow()
-    const visible = await webWin.isVisible()
-    if (!visible) {
-      const { x, y } = await cursorPosition()
-
-      // 获取窗口大小
-      const size = await webWin.outerSize()
-      const monitor = await currentMonitor()
-
-      // 计算窗口位置，确保不会超出屏幕边界
-      let posX = x
-      let posY = y
-
-      if (monitor) {
-        const { width: screenWidth, height: screenHeight } = monitor.size
-
-        // 如果窗口右边界超出屏幕，则向左偏移
-        if (x + size.width > screenWidth) {
-          posX = screenWidth - size.width
-        }
-
-        // 如果窗口下边界超出屏幕，则向上偏移
-        if (y + size.height > screenHeight) {
-          posY = screenHeight - size.height
-        }
+async function applyHotkeyStatuses(statuses: HotkeyStatus[], notify: boolean): Promise<void> {
+  const quickPasteFailedKeys = []
+  for (const status of statuses) {
+    const available = status.result.state === 'registered'
+    if (status.action === 'toggleWindow') {
+      await saveWakeUpRoutineKeyAvailable(available)
+      if (!available && status.result.state !== 'disabled' && notify) {
+        sendNotification({
+          title: 'EasyPaste',
+          body: currentLanguage.value.pages.list.registerOpenWindowShortcutKeysErrorHint.replace(
+            '{keys}',
+            formatKeysDisplay(status.shortcut.split('+'))
+          ),
+        })
       }
-
-      // 设置窗口位置并显示
-      await webWin.setPosition(new PhysicalPosition(posX, posY))
-      await webWin.show()
-      await webWin.setFocus()
-      window.focus()
-      document.body.focus()
-      document.body.click()
-    } else {
-      await hideWindow()
-    }
-  })
-}
-
-/**
- * 打开当前窗口快捷键注册
- */
-async function registerShortcutKeysOpenWindow(): Promise<void> {
-  console.log('注册快捷键打开当前窗口')
-  const wakeUpRoutine = await getWakeUpRoutine()
-  if (wakeUpRoutine && wakeUpRoutine.key && wakeUpRoutine.key.length > 0) {
-    try {
-      const shortcutKeys = convertRegisterKeys(wakeUpRoutine.key)
-      const registered = await isRegistered(shortcutKeys)
-      if (registered) {
-        console.debug('快捷键已经注册，注销后重新注注册', shortcutKeys)
-        await unregister(shortcutKeys)
+    } else if (status.action === 'pasteRecent' && status.index) {
+      await saveQuickPasteKeyAvailable(status.index, available)
+      if (!available && status.result.state !== 'disabled') {
+        quickPasteFailedKeys.push(formatKeysDisplay(status.shortcut.split('+')))
       }
-      await registerOpenWindowKey(shortcutKeys)
-      saveWakeUpRoutineKeyAvailable(true)
-    } catch (ex) {
-      error('打开程序快捷键注册失败，' + ex)
-      console.error(ex)
-      sendNotification({
-        title: 'EasyPaste',
-        body: currentLanguage.value.pages.list.registerOpenWindowShortcutKeysErrorHint.replace(
-          '{keys}',
-          formatKeysDisplay(wakeUpRoutine.key)
-        ),
-      })
-      saveWakeUpRoutineKeyAvailable(false)
     }
   }
-}
-
-/**
- * 注册快捷键快速粘贴数据
- */
-async function registerQuickPasteKey(
-  shortcutKeys: string,
-  number: number,
-  message: MessageApiInjection
-): Promise<void> {
-  console.debug('注册快捷键快速粘贴数据: ' + shortcutKeys)
-  await register(shortcutKeys, async event => {
-    // 只监听按键按下，松开忽略
-    if (event.state === 'Released') {
-      return
-    }
-    const item = clipboardItems.value[number - 1]
-    if (item) {
-      await onCopy(item, message)
-    }
-  })
-}
-
-/**
- * 注册快捷键快速粘贴数据
- */
-async function registerShortcutKeysQuickPaste(message: MessageApiInjection): Promise<void> {
-  console.log('注册快捷键快速粘贴数据')
-  const quickPaste = await getQuickPaste()
-  if (quickPaste && quickPaste.key && quickPaste.key.length > 0) {
-    const registerFailedKeys = []
-    for (let i = 1; i <= 9; i++) {
-      const keys = [...quickPaste.key, String(i)]
-      try {
-        const shortcutKeys = convertRegisterKeys(keys)
-        const registered = await isRegistered(shortcutKeys)
-        if (registered) {
-          console.debug('快捷键已经注册，注销后重新注注册', shortcutKeys)
-          await unregister(shortcutKeys)
-        }
-        await registerQuickPasteKey(shortcutKeys, i, message)
-        saveQuickPasteKeyAvailable(i, true)
-      } catch (ex) {
-        error('快速粘贴快捷键注册失败，' + ex)
-        console.error(ex)
-        saveQuickPasteKeyAvailable(i, false)
-        registerFailedKeys.push(formatKeysDisplay(keys))
-      }
-    }
-    if (registerFailedKeys.length > 0) {
-      sendNotification({
-        title: 'EasyPaste',
-        body: currentLanguage.value.pages.list.registerQuickPasteShortcutKeysErrorHint.replace(
-          '{keys}',
-          registerFailedKeys.join(',')
-        ),
-      })
-    }
+  if (quickPasteFailedKeys.length > 0 && notify) {
+    sendNotification({
+      title: 'EasyPaste',
+      body: currentLanguage.value.pages.list.registerQuickPasteShortcutKeysErrorHint.replace(
+        '{keys}',
+        quickPasteFailedKeys.join(',')
+      ),
+    })
   }
 }
 
@@ -285,46 +178,40 @@ async function initCheckUpdateListener(): Promise<UnlistenFn> {
 }
 
 /**
- * 初始化修改快捷键打开当前窗口监听
+ * 初始化快捷键重新注册监听，设置页面修改快捷键后由后端重新注册
+ */
+let hotkeysRegisteredListener: any = null
+
+async function initHotkeysRegisteredListener(): Promise<UnlistenFn> {
+  return await listen<HotkeyStatus[]>('hotkeys-registered', async event => {
+    await applyHotkeyStatuses(event.payload, false)
+  })
+}
+
+/**
+ * 初始化快捷键快速粘贴监听
  */
-let updateRegisterShortcutKeysOpenWindowListener: any = null
-
-async function initUpdateRegisterShortcutKeysOpenWindowListener(): Promise<UnlistenFn> {
-  return await listen('update-open-window-key', async (event: any) => {
-    const keys: ShortcutKeys = event.payload.keys
-    if (keys.wakeUpRoutine && keys.wakeUpRoutine.key && keys.wakeUpRoutine.key.length > 0) {
-      const shortcutKeys = convertRegisterKeys(keys.wakeUpRoutine.key)
-      await registerOpenWindowKey(shortcutKeys)
+let quickPasteListener: any = null
+
+async function initQuickPasteListener(message: MessageApiInjection): Promise<UnlistenFn> {
+  return await listen('quick-paste', async (event: any) => {
+    const item = clipboardItems.value[event.payload.index - 1]
+    if (item) {
+      await onPaste(item, message)
     }
   })
 }
 
 /**
- * 初始化修改快捷键打开当前窗口监听
+ * 初始化快捷键打开搜索框监听
  */
-let updateRegisterShortcutKeysQuickPasteListener: any = null
-
-async function initUpdateRegisterShortcutKeysQuickPasteListener(
-  message: MessageApiInjection
-): Promise<UnlistenFn> {
-  return await listen('update-quick-paste-key', async (event: any) => {
-    const keys: ShortcutKeys = event.payload.keys
-    if (keys.quickPaste && keys.quickPaste.key && keys.quickPaste.key.length > 0) {
-      for (let i = 1; i <= 9; i++) {
-        const keysArr = [...keys.quickPaste.key, String(i)]
-        try {
-          const shortcutKeys = convertRegisterKeys(keysArr)
-          const registered = await isRegistered(shortcutKeys)
-          if (registered) {
-            console.debug('快捷键已经注册，注销后重新注注册', shortcutKeys)
-            await unregister(shortcutKeys)
-          }
-          await registerQuickPasteKey(shortcutKeys, i, message)
-        } catch (ex) {
-          error('快速粘贴快捷键注册失败，' + ex)
-          console.error(ex)
-        }
-      }
+let openSearchListener: any = null
+
+async function initOpenSearchListener(): Promise<UnlistenFn> {
+  return await listen('open-search', async () => {
+    await getCurrentWebviewWindow().setFocus()
+    if (!showSearchBox.value) {
+      toggleSearchBox()
     }
   })
 }
@@ -376,9 +263,9 @@ function initUpdateAlwaysOnTopListener(): Promise<UnlistenFn> {
 let updateListeningStatusListener: any = null
 
 function initUpdateListeningStatusListener(): Promise<UnlistenFn> {
-  return listen('update-listening', async (event: any) => {
-    console.log(event.payload)
-    isListening.value = event.payload
+  return listen<ListeningStatus>('listening-status-change', async event => {
+    listeningStatus.value = event.payload
+    isListening.value = event.payload.status === 'listening'
   })
 }
 
@@ -418,18 +305,14 @@ export async function initializeWindow(message: MessageApiInjection): Promise<vo
     // 添加检查更新事件监听
     checkUpdateUnListener = await initCheckUpdateListener()
 
-    // 注册快捷键快速粘贴
-    registerShortcutKeysQuickPaste(message).catch(e => {
-      error('注册快捷键快速粘贴失败' + e)
-    })
+    // 添加快捷键快速粘贴事件监听
+    quickPasteListener = await initQuickPasteListener(message)
 
-    // 添加修改快捷键打开当前窗口事件监听
-    updateRegisterShortcutKeysOpenWindowListener =
-      await initUpdateRegisterShortcutKeysOpenWindowListener()
+    // 添加快捷键打开搜索框事件监听
+    openSearchListener = await initOpenSearchListener()
 
-    // 添加修改快捷键快速粘贴事件监听
-    updateRegisterShortcutKeysQuickPasteListener =
-      await initUpdateRegisterShortcutKeysQuickPasteListener(message)
+    // 添加快捷键重新注册事件监听
+    hotkeysRegisteredListener = await initHotkeysRegisteredListener()
 
     // 添加更新自动隐藏窗口事件监听
     updateAutoHideWindowListener = await initUpdateAutoHideWindowListener()
@@ -437,13 +320,16 @@ export async function initializeWindow(message: MessageApiInjection): Promise<vo
     // 添加修改窗口置顶状态事件监听
     updateAlwaysOnTopListener = await initUpdateAlwaysOnTopListener()
 
-    // 注册快捷键打开当前窗口
-    registerShortcutKeysOpenWindow().catch(e => {
-      error('注册快捷键打开当前窗口失败' + e)
-    })
+    // 全局快捷键由后端注册，这里只保存可用状态并提示注册失败的快捷键
+    invoke<HotkeyStatus[]>('get_hotkey_statuses')
+      .then(statuses => applyHotkeyStatuses(statuses, true))
+      .catch(e => {
+        error('获取快捷键注册结果失败' + e)
+      })
 
     // 初始化是否监听剪贴板
-    isListening.value = await invoke<boolean>('is_listening')
+    listeningStatus.value = await invoke<ListeningStatus>('get_listening_status')
+    isListening.value = listeningStatus.value.status === 'listening'
 
     // 添加修改是否监听剪贴板事件监听
     updateListeningStatusListener = initUpdateListeningStatusListener()
@@ -480,15 +366,14 @@ export function destroyWindow(): void {
   if (checkUpdateUnListener) {
     checkUpdateUnListener()
   }
-  // 清除修改快捷键打开当前窗口事件监听
-  if (updateRegisterShortcutKeysOpenWindowListener) {
-    updateRegisterShortcutKeysOpenWindowListener()
-  }
+  // 清除快捷键重新注册事件监听
+  hotkeysRegisteredListener?.()
 
-  // 清除修改快捷键快速粘贴事件监听
-  if (updateRegisterShortcutKeysQuickPasteListener) {
-    updateRegisterShortcutKeysQuickPasteListener()
-  }
+  // 清除快捷键快速粘贴事件监听
+  quickPasteListener?.()
+
+  // 清除快捷键打开搜索框事件监听
+  openSearchListener?.()
 
   // 添加更新自动隐藏窗口事件监听
   if (updateAutoHideWindowListener) {
@@ -1,16 +1,14 @@
 import { reactive, ref } from 'vue'
 import { loadPluginManifest } from '../../../services/PluginService.ts'
-import { dirname, join } from '@tauri-apps/api/path'
-import { exists, mkdir, readFile, writeFile } from '@tauri-apps/plugin-fs'
-import { deleteFolder } from '../../../utils/FileUtil.ts'
+import { join, tempDir } from '@tauri-apps/api/path'
+import { exists, readTextFile, writeFile } from '@tauri-apps/plugin-fs'
+import { deleteFile } from '../../../utils/FileUtil.ts'
 import { fetch } from '@tauri-apps/plugin-http'
 import { isDev, isMac } from '../../../data/SystemParams.ts'
 import ClipboardDBService from '../../../services/ClipboardDBService.ts'
-import { BlobReader, BlobWriter, ZipReader } from '@zip.js/zip.js'
 import { error } from '@tauri-apps/plugin-log'
 import { emit } from '@tauri-apps/api/event'
 import { currentLanguage, loadPluginLanguage } from '../../../services/LanguageService.ts'
-import { getPluginPath } from '../../../store/Settings.ts'
 import { invoke } from '@tauri-apps/api/core'
 import { open } from '@tauri-apps/plugin-dialog'
 import { MessageApiInjection } from 'naive-ui/es/message/src/MessageProvider'
@@ -26,6 +24,8 @@ export const selectedPlugin = reactive({} as SelectPlugin)
 export const loadingMap = ref<Map<string, loadingState>>(new Map<string, loadingState>())
 export const storeListLoading = ref(false)
 export const localListLoading = ref(false)
+// 缺少 plugin.json 需要重新安装的插件id
+export const manifestMissingIds = ref<Set<string>>(new Set<string>())
 
 /**
  * 判断插件是否有更新
@@ -76,146 +76,218 @@ export const getLocalPlugin = (id: string): LocalPlugin | null => {
 }
 
 /**
- * 安装插件文件
+ * 下载插件安装包到临时目录
  * @param plugin 插件信息
- * @param pluginFolderPath 插件安装目录
+ * @returns 安装包路径
+ */
+async function downloadPluginArchive(plugin: StorePlugin): Promise<string> {
+  const response = await fetch(plugin.downloadUrl, { method: 'GET' })
+  if (!response.ok) {
+    throw new Error('插件下载失败: ' + response.status)
+  }
+  // 后端根据文件后缀判断安装包格式
+  const url = plugin.downloadUrl.toLowerCase()
+  const suffix = url.endsWith('.tar.gz') || url.endsWith('.tgz') ? '.tar.gz' : '.zip'
+  const archivePath = await join(await tempDir(), `${plugin.id}-${Date.now()}${suffix}`)
+  await writeFile(archivePath, new Uint8Array(await response.arrayBuffer()))
+  return archivePath
+}
+
+/**
+ * 校验安装包并读取插件清单
+ * @param archivePath 安装包路径
+ * @param sha256 校验和，本地安装包没有校验和
+ * @param signature 签名
+ */
+async function inspectPluginArchive(
+  archivePath: string,
+  sha256: string | null,
+  signature: string
+): Promise<PluginManifest> {
+  return await invoke<PluginManifest>('inspect_plugin_archive', {
+    archivePath,
+    sha256,
+    signature,
+  })
+}
+
+/**
+ * 插件申请权限时，安装或授权前让用户确认
+ * @param permissions 插件申请的权限
+ * @param pluginName 插件名称
+ * @param dialog 对话框
+ * @returns 用户是否同意
+ */
+async function confirmPermissions(
+  permissions: string[],
+  pluginName: string,
+  dialog: DialogApiInjection
+): Promise<boolean> {
+  if (permissions.length === 0) {
+    return true
+  }
+  const hints = currentLanguage.value.pages.pluginStore
+  return await new Promise<boolean>(resolve => {
+    dialog.warning({
+      title: hints.permissionTitle,
+      content:
+        hints.permissionContent.replace('${pluginName}', pluginName) +
+        '\n' +
+        permissions.join('\n') +
+        '\n\n' +
+        hints.permissionHostApiHint,
+      positiveText: hints.permissionConfirmBtn,
+      negativeText: hints.permissionCancelBtn,
+      onPositiveClick: () => resolve(true),
+      onNegativeClick: () => resolve(false),
+      onClose: () => resolve(false),
+    })
+  })
+}
+
+/**
+ * 获取已安装插件还没有授权的权限，插件清单不可用时返回空
+ * @param pluginId 插件id
+ */
+export const getUnapprovedPermissions = async (pluginId: string): Promise<string[]> => {
+  try {
+    const permissions = await invoke<PluginPermissions>('get_plugin_permissions', { pluginId })
+    return permissions.requested.filter(p => !permissions.approved.includes(p))
+  } catch (e) {
+    return []
+  }
+}
+
+/**
+ * 让用户确认并授权已安装插件申请的权限，旧版本安装的插件没有授权信息时无法启动
+ * @param pluginId 插件id
+ * @param pluginName 插件名称
  * @param message 消息框架
+ * @param dialog 对话框
+ * @returns 是否已授权
  */
-async function installPlugin(
-  plugin: StorePlugin,
-  pluginFolderPath: string,
-  message: MessageApiInjection
-) {
+export const approvePermissions = async (
+  pluginId: string,
+  pluginName: string,
+  message: MessageApiInjection,
+  dialog: DialogApiInjection
+): Promise<boolean> => {
   try {
-    // 下载并解压文件
-    await installPluginFile(plugin.downloadUrl, pluginFolderPath, plugin.id)
-    // 保存插件信息
-    await savePluginInfo(plugin)
+    const permissions = await invoke<PluginPermissions>('get_plugin_permissions', { pluginId })
+    if (!(await confirmPermissions(permissions.requested, pluginName, dialog))) {
+      return false
+    }
+    await invoke('approve_plugin_permissions', { pluginId, permissions: permissions.requested })
+    message.success(currentLanguage.value.pages.pluginStore.approveSuccessHint)
     return true
-  } catch (error) {
-    console.error('下载失败:', error)
-    message.error(currentLanguage.value.pages.pluginStore.installFailedHint)
-    deleteFolder(pluginFolderPath)
-    loadingMap.value.delete(plugin.id)
+  } catch (e) {
+    error('插件授权失败: ' + JSON.stringify(e))
+    message.error(currentLanguage.value.pages.pluginStore.approveFailedHint)
     return false
   }
 }
 
 /**
- * 安装插件
+ * 根据安装错误类型提示用户
+ * @param e 安装错误
+ * @param defaultHint 默认提示
+ * @param message 消息框架
+ */
+function showInstallError(e: any, defaultHint: string, message: MessageApiInjection): void {
+  const hints = currentLanguage.value.pages.pluginStore
+  switch (e?.kind) {
+    case 'noTrustedKeys':
+      message.error(hints.noTrustedKeysHint)
+      break
+    case 'invalidSignature':
+      message.error(hints.invalidSignatureHint)
+      break
+    case 'untrustedSignature':
+      message.error(hints.untrustedSignatureHint)
+      break
+    case 'checksumMismatch':
+      message.error(hints.checksumMismatchHint)
+      break
+    case 'invalidManifest':
+      message.error(hints.localInstallInvalidManifestHint)
+      break
+    default:
+      message.error(defaultHint)
+  }
+}
+
+/**
+ * 安装插件，安装包由后端校验校验和、签名和插件清单后解压
  */
-export const install = async (pluginId: string, message: MessageApiInjection): Promise<void> => {
+export const install = async (
+  pluginId: string,
+  message: MessageApiInjection,
+  dialog: DialogApiInjection
+): Promise<void> => {
   const plugin = pluginStore.value.find(p => p.id === pluginId)
   if (plugin) {
+    if (!plugin.downloadUrl) {
+      message.error(currentLanguage.value.pages.pluginStore.installNotUrlHint)
+      return
+    }
+    let archivePath = ''
     try {
       console.log('开始安装插件', plugin)
       loadingMap.value.set(plugin.id, 'downloading')
-      const configDir = await getPluginPath()
-      const pluginFolderPath = await join(configDir, plugin.id)
-      // 删除已安装的插件
-      if (await exists(pluginFolderPath)) {
-        await deleteFolder(pluginFolderPath)
+      archivePath = await downloadPluginArchive(plugin)
+      loadingMap.value.set(plugin.id, 'unzipping')
+      const sha256 = plugin.sha256 || null
+      const signature = plugin.signature || ''
+      const manifest = await inspectPluginArchive(archivePath, sha256, signature)
+      if (manifest.id !== plugin.id) {
+        error('插件清单id与商店插件id不一致: ' + manifest.id)
+        message.error(currentLanguage.value.pages.pluginStore.installFailedHint)
+        return
       }
-      console.log('插件安装目录', pluginFolderPath)
-      console.log('开始下载文件', plugin, plugin.downloadUrl)
-      if (plugin && plugin.downloadUrl) {
-        if (!(await installPlugin(plugin, pluginFolderPath, message))) {
-          console.log('安装失败', pluginId)
-          return
-        }
-        console.log('安装成功', pluginId)
-        loadingMap.value.set(plugin.id, 'loading')
-        // 重新加载插件
-        await loadLocalPlugins()
-        // 后端载入插件的语言
-        await invoke('load_plugin_locales', { pluginId: pluginId })
-        // 前端获取插件的语言
-        await loadPluginLanguage()
-
-        // 如果当前选中的插件是刚安装的，更新选中状态以触发设置按钮检查
-        if (selectedPlugin.pluginId === pluginId) {
-          const newlyInstalled = localPlugins.value.find(l => l.plugin_id === pluginId)
-          if (newlyInstalled) {
-            onSelectLocal(newlyInstalled)
-          }
+      if (!(await confirmPermissions(manifest.permissions, plugin.name, dialog))) {
+        return
+      }
+      await invoke('install_plugin', {
+        archivePath,
+        sha256,
+        signature,
+        approvedPermissions: manifest.permissions,
+      })
+      // 保存插件信息
+      await savePluginInfo(plugin)
+      console.log('安装成功', pluginId)
+      loadingMap.value.set(plugin.id, 'loading')
+      // 重新加载插件
+      await loadLocalPlugins()
+      // 前端获取插件的语言，后端在安装时已经载入
+      await loadPluginLanguage()
+
+      // 如果当前选中的插件是刚安装的，更新选中状态以触发设置按钮检查
+      if (selectedPlugin.pluginId === pluginId) {
+        const newlyInstalled = localPlugins.value.find(l => l.plugin_id === pluginId)
+        if (newlyInstalled) {
+          onSelectLocal(newlyInstalled)
         }
-        // 保存插件配置
-        message.success(
-          currentLanguage.value.pages.pluginStore.installSuccessHint.replace(
-            '${pluginName}',
-            plugin.name
-          )
-        )
-        await emit('install-plugin', { pluginId: pluginId })
-      } else {
-        message.error(currentLanguage.value.pages.pluginStore.installNotUrlHint)
       }
+      message.success(
+        currentLanguage.value.pages.pluginStore.installSuccessHint.replace(
+          '${pluginName}',
+          plugin.name
+        )
+      )
+      await emit('install-plugin', { pluginId: pluginId })
     } catch (e) {
-      console.log('安装插件失败', e)
+      error('安装插件失败: ' + JSON.stringify(e))
+      showInstallError(e, currentLanguage.value.pages.pluginStore.installFailedHint, message)
     } finally {
       loadingMap.value.delete(plugin.id)
+      if (archivePath) {
+        await deleteFile(archivePath)
+      }
     }
   }
 }
 
-/**
- * 安装插件文件
- * @param url 插件下载地址
- * @param pluginFolderPath 插件安装目录
- */
-async function installPluginFile(
-  url: string,
-  pluginFolderPath: string,
-  pluginId: string
-): Promise<void> {
-  // 文件下载
-  const response = await fetch(url, { method: 'GET' })
-  console.log('文件下载完成，开始解压文件', response)
-  loadingMap.value.set(pluginId, 'unzipping')
-  await mkdir(pluginFolderPath, { recursive: true })
-  console.log('文件保存目录', pluginFolderPath)
-  // 文件解压
-  const blob = await response.blob()
-  const reader = new ZipReader(new BlobReader(blob))
-  const entries = await reader.getEntries()
-  let processedFiles = 0
-
-  for (const entry of entries) {
-    // 移除根目录前缀
-    let relativePath = entry.filename
-    console.log('原始文件名', entry.filename)
-    console.log('解压文件', entry.filename, '保存路径', relativePath)
-    if (!relativePath || relativePath === '/' || relativePath === '\\') {
-      console.log('是根目录，跳过', entry.filename)
-      continue
-    }
-
-    if (!entry.directory) {
-      console.log('是文件，生成文件')
-      const data = await entry.getData(new BlobWriter())
-      const targetPath = await join(pluginFolderPath, relativePath)
-      console.log('保存文件', targetPath)
-
-      const parentDir = await dirname(targetPath)
-      await mkdir(parentDir, { recursive: true })
-
-      await writeFile(targetPath, new Uint8Array(await data.arrayBuffer()))
-      console.log('文件保存完成', targetPath)
-    } else if (relativePath !== '') {
-      console.log('是目录，生成目录')
-      // 处理子目录
-      const targetPath = await join(pluginFolderPath, relativePath)
-      await mkdir(targetPath, { recursive: true })
-      console.log('目录保存完成', targetPath)
-    }
-
-    processedFiles++
-  }
-
-  await reader.close()
-  console.log('文件解压完成，已处理文件数', processedFiles)
-}
-
 /**
  * 保存插件信息
  * @param value 插件信息
@@ -239,7 +311,7 @@ async function savePluginInfo(value: StorePlugin): Promise<void> {
 }
 
 /**
- * 卸载插件
+ * 卸载插件，由后端停止插件进程并删除插件目录
  */
 export const uninstall = async (pluginId: string, message: MessageApiInjection): Promise<void> => {
   const plugin = localPlugins.value.find(p => p.plugin_id === pluginId)
@@ -249,13 +321,17 @@ export const uninstall = async (pluginId: string, message: MessageApiInjection):
       loadingMap.value.set(plugin.plugin_id, 'uninstalling')
       if (plugin.id && plugin.id > 0) {
         await emit('uninstall-plugin', { pluginId: plugin.plugin_id })
+        try {
+          await invoke('uninstall_plugin', { pluginId: plugin.plugin_id })
+        } catch (e: any) {
+          // 插件目录已经不存在时只删除插件数据
+          if (e?.kind !== 'notInstalled') {
+            throw e
+          }
+        }
         // 删除插件数据
         const db = await ClipboardDBService.getInstance()
         await db.removePlugin(plugin.id)
-        // 删除插件文件夹
-        const configDir = await getPluginPath()
-        const pluginFolderPath = await join(configDir, `${plugin.plugin_id}`)
-        await deleteFolder(pluginFolderPath)
         // 重新加载插件
         await loadLocalPlugins()
         message.success(currentLanguage.value.pages.pluginStore.uninstallSuccessHint)
@@ -264,7 +340,7 @@ export const uninstall = async (pluginId: string, message: MessageApiInjection):
         await loadLocalPlugins()
       }
     } catch (e) {
-      console.error(e)
+      error('插件卸载失败: ' + JSON.stringify(e))
       message.error(currentLanguage.value.pages.pluginStore.uninstallFailedHint)
     } finally {
       loadingMap.value.delete(plugin.plugin_id)
@@ -273,81 +349,96 @@ export const uninstall = async (pluginId: string, message: MessageApiInjection):
 }
 
 /**
- * 插件更新
+ * 插件更新，升级失败时后端会恢复旧版本
  */
-export const update = async (pluginId: string, message: MessageApiInjection): Promise<void> => {
-  if (pluginId) {
+export const update = async (
+  pluginId: string,
+  message: MessageApiInjection,
+  dialog: DialogApiInjection
+): Promise<void> => {
+  if (!pluginId) {
+    message.error(currentLanguage.value.pages.pluginStore.notSelectPluginHint)
+    return
+  }
+  const storePlugin = pluginStore.value.find(l => l.id === pluginId)
+  const localPlugin = localPlugins.value.find(l => l.plugin_id === pluginId)
+  if (!storePlugin) {
+    message.error(currentLanguage.value.pages.pluginStore.notPluginHint)
+    return
+  }
+  if (!localPlugin) {
+    // 本地没有安装插件，执行安装操作
+    await install(pluginId, message, dialog)
+    return
+  }
+  if (!storePlugin.downloadUrl) {
+    message.error(currentLanguage.value.pages.pluginStore.installNotUrlHint)
+    return
+  }
+  let archivePath = ''
+  try {
+    console.log('开始更新插件', storePlugin, storePlugin.downloadUrl)
+    loadingMap.value.set(pluginId, 'downloading')
+    archivePath = await downloadPluginArchive(storePlugin)
     loadingMap.value.set(pluginId, 'updating')
-    const storePlugin = pluginStore.value.find(l => l.id === pluginId)
-    const localPlugin = localPlugins.value.find(l => l.plugin_id === pluginId)
-    if (storePlugin) {
-      if (localPlugin) {
-        if (storePlugin && storePlugin.downloadUrl) {
-          try {
-            console.log('开始更新插件', storePlugin, storePlugin.downloadUrl)
-            loadingMap.value.set(storePlugin.id, 'uninstalling')
-            const configDir = await getPluginPath()
-            const pluginFolderPath = await join(configDir, selectedPlugin.pluginId)
-            console.log('插件安装目录', pluginFolderPath)
-            // 卸载插件
-            try {
-              await emit('uninstall-plugin', { pluginId: pluginId })
-              await deleteFolder(pluginFolderPath)
-            } catch (e) {
-              error('插件卸载失败' + e)
-              console.log('插件卸载失败', e)
-              message.error(currentLanguage.value.pages.pluginStore.updateUnInstallFailedHint)
-              return
-            }
-            loadingMap.value.set(storePlugin.id, 'downloading')
-            // 安装插件
-            try {
-              console.log('开始下载文件', storePlugin, storePlugin.downloadUrl)
-              // 下载并解压文件
-              await installPluginFile(storePlugin.downloadUrl, pluginFolderPath, storePlugin.id)
-              // 保存插件信息
-              await updatePluginInfo(localPlugin.id, storePlugin)
-              console.log('安装成功', pluginId)
-
-              loadingMap.value.set(storePlugin.id, 'loading')
-              // 后端载入插件的语言
-              await invoke('load_plugin_locales', { pluginId: pluginId })
-              // 前端获取插件的语言
-              await loadPluginLanguage()
-              // 重新加载插件
-              await loadLocalPlugins()
-              await emit('install-plugin', { pluginId: pluginId })
-              // 保存插件配置
-              message.success(
-                currentLanguage.value.pages.pluginStore.updateSuccessHint.replace(
-                  '${pluginName}',
-                  storePlugin.name
-                )
-              )
-            } catch (e) {
-              error('插件安装失败' + e)
-              await deleteFolder(pluginFolderPath)
-              const db = await ClipboardDBService.getInstance()
-              await db.removePlugin(localPlugin.id)
-              message.error(currentLanguage.value.pages.pluginStore.updateFailedHint)
-              await loadLocalPlugins()
-            }
-          } finally {
-            loadingMap.value.delete(storePlugin.id)
-          }
-        } else {
-          message.error(currentLanguage.value.pages.pluginStore.installNotUrlHint)
-        }
-      } else {
-        // 本地没有安装插件，执行安装操作
-        await install(pluginId, message)
-      }
-    } else {
-      message.error(currentLanguage.value.pages.pluginStore.notPluginHint)
+    const sha256 = storePlugin.sha256 || null
+    const signature = storePlugin.signature || ''
+    const manifest = await inspectPluginArchive(archivePath, sha256, signature)
+    if (manifest.id !== pluginId) {
+      error('插件清单id与商店插件id不一致: ' + manifest.id)
+      message.error(currentLanguage.value.pages.pluginStore.updateFailedHint)
+      return
     }
+    if (!(await confirmPermissions(manifest.permissions, storePlugin.name, dialog))) {
+      return
+    }
+    await invoke('upgrade_plugin', {
+      archivePath,
+      sha256,
+      signature,
+      approvedPermissions: manifest.permissions,
+    })
+    // 保存插件信息
+    await updatePluginInfo(localPlugin.id, storePlugin)
+    console.log('更新成功', pluginId)
+
+    loadingMap.value.set(pluginId, 'loading')
+    // 前端获取插件的语言，后端在升级时已经载入
+    await loadPluginLanguage()
+    // 重新加载插件
+    await loadLocalPlugins()
+    // 卸载旧版本的页面并加载新版本
+    await emit('uninstall-plugin', { pluginId: pluginId })
+    await emit('install-plugin', { pluginId: pluginId })
+    message.success(
+      currentLanguage.value.pages.pluginStore.updateSuccessHint.replace(
+        '${pluginName}',
+        storePlugin.name
+      )
+    )
+  } catch (e) {
+    error('插件更新失败: ' + JSON.stringify(e))
+    showInstallError(e, currentLanguage.value.pages.pluginStore.updateFailedHint, message)
+  } finally {
     loadingMap.value.delete(pluginId)
+    if (archivePath) {
+      await deleteFile(archivePath)
+    }
+  }
+}
+
+/**
+ * 重新安装旧版本安装、缺少 plugin.json 的插件，商店中没有该插件时从本地文件安装
+ */
+export const reinstall = async (
+  pluginId: string,
+  message: MessageApiInjection,
+  dialog: DialogApiInjection
+): Promise<void> => {
+  if (pluginStore.value.some(p => p.id === pluginId)) {
+    await update(pluginId, message, dialog)
   } else {
-    message.error(currentLanguage.value.pages.pluginStore.notSelectPluginHint)
+    await installFromLocalFile(message, dialog)
   }
 }
 
@@ -390,7 +481,7 @@ async function updatePluginInfo(id: number, value: StorePlugin): Promise<void> {
       plugin_id: value.id,
       plugin_name: value.name,
       version: value.version,
-      use_location: JSON.stringify(useLocationSet),
+      use_location: JSON.stringify([...useLocationSet]),
       platform: value.platform,
       url: value.downloadUrl,
       description: value.description,
@@ -518,6 +609,7 @@ async function loadLocalPlugins(): Promise<void> {
     const plugins = await db.getAllPlugins()
     console.log('本地插件数据：', plugins)
     localPlugins.value = plugins
+    manifestMissingIds.value = await findManifestMissing(plugins)
   } catch (e) {
     error('获取本地插件数据异常' + e)
   } finally {
@@ -525,6 +617,24 @@ async function loadLocalPlugins(): Promise<void> {
   }
 }
 
+/**
+ * 查找旧版本安装、缺少 plugin.json 的插件
+ * @param plugins 本地插件
+ */
+async function findManifestMissing(plugins: LocalPlugin[]): Promise<Set<string>> {
+  const missing = new Set<string>()
+  for (const plugin of plugins) {
+    try {
+      await invoke('get_plugin_manifest', { pluginId: plugin.plugin_id })
+    } catch (e: any) {
+      if (e?.kind === 'manifestMissing') {
+        missing.add(plugin.plugin_id)
+      }
+    }
+  }
+  return missing
+}
+
 /**
  * 比较版本号
  * @param version1 版本号1
@@ -548,7 +658,7 @@ function compareVersion(version1: string, version2: string): number {
 }
 
 /**
- * 从本地文件安装插件
+ * 从本地文件安装插件，签名文件为同目录下的 <安装包文件名>.sig
  */
 export const installFromLocalFile = async (
   message: MessageApiInjection,
@@ -561,7 +671,7 @@ export const installFromLocalFile = async (
       filters: [
         {
           name: 'Plugin Package',
-          extensions: ['zip'],
+          extensions: ['zip', 'tgz', 'gz'],
         },
       ],
     })
@@ -572,56 +682,24 @@ export const installFromLocalFile = async (
     }
 
     console.log('选择的文件路径:', selected)
-    loadingMap.value.set('local-file-install', 'downloading')
-
-    // 读取 zip 文件
-    const fileContent = await readFile(selected as string)
-    const blob = new Blob([fileContent])
-
-    // 先读取 manifest.json 获取插件信息
-    const reader = new ZipReader(new BlobReader(blob))
-    const entries = await reader.getEntries()
-
-    // 查找 manifest.json 文件
-    const manifestEntry = entries.find(
-      entry => entry.filename.endsWith('manifest.json') || entry.filename.includes('/manifest.json')
-    )
-
-    if (!manifestEntry) {
-      message.error(currentLanguage.value.pages.pluginStore.localInstallNoManifestHint)
-      loadingMap.value.delete('local-file-install')
-      await reader.close()
-      return
-    }
-
-    if (manifestEntry.directory) {
-      loadingMap.value.delete('local-file-install')
-      await reader.close()
+    const archivePath = selected as string
+    const signaturePath = archivePath + '.sig'
+    if (!(await exists(signaturePath))) {
+      message.error(currentLanguage.value.pages.pluginStore.localInstallNoSignatureHint)
       return
     }
+    const signature = await readTextFile(signaturePath)
+    loadingMap.value.set('local-file-install', 'unzipping')
 
-    // 读取并解析 manifest.json
-    const manifestData = await manifestEntry.getData(new BlobWriter())
-    const manifestText = await manifestData.text()
-    const manifest = JSON.parse(manifestText)
-
+    // 校验安装包并读取插件清单
+    const manifest = await inspectPluginArchive(archivePath, null, signature)
     console.log('插件清单:', manifest)
-
-    // 验证必要的字段
-    if (!manifest.id || !manifest.name || !manifest.version) {
-      message.error(currentLanguage.value.pages.pluginStore.localInstallInvalidManifestHint)
-      loadingMap.value.delete('local-file-install')
-      await reader.close()
-      return
-    }
-
     const pluginId = manifest.id
-    const configDir = await getPluginPath()
-    const pluginFolderPath = await join(configDir, pluginId)
+    const pluginName = manifest.name || pluginId
 
-    // 检查是否已安装
+    // 检查是否已安装，缺少 plugin.json 的插件直接重新安装
     const existingPlugin = localPlugins.value.find(p => p.plugin_id === pluginId)
-    if (existingPlugin) {
+    if (existingPlugin && !manifestMissingIds.value.has(pluginId)) {
       console.log('插件已存在，检查版本', existingPlugin.version, manifest.version)
 
       // 比较版本号
@@ -633,19 +711,17 @@ export const installFromLocalFile = async (
         if (compareResult === 0) {
           message.warning(
             currentLanguage.value.pages.pluginStore.localInstallSameVersionHint
-              .replace('${pluginName}', manifest.name)
+              .replace('${pluginName}', pluginName)
               .replace('${version}', manifest.version)
           )
         } else {
           message.warning(
             currentLanguage.value.pages.pluginStore.localInstallOldVersionHint
-              .replace('${pluginName}', manifest.name)
+              .replace('${pluginName}', pluginName)
               .replace('${newVersion}', manifest.version)
               .replace('${currentVersion}', existingPlugin.version)
           )
         }
-        loadingMap.value.delete('local-file-install')
-        await reader.close()
         return
       }
 
@@ -654,7 +730,7 @@ export const installFromLocalFile = async (
         dialog.warning({
           title: currentLanguage.value.pages.pluginStore.localInstallUpdateTitle,
           content: currentLanguage.value.pages.pluginStore.localInstallUpdateContent
-            .replace('${pluginName}', manifest.name)
+            .replace('${pluginName}', pluginName)
             .replace('${oldVersion}', existingPlugin.version)
             .replace('${newVersion}', manifest.version),
           positiveText: currentLanguage.value.pages.pluginStore.localInstallUpdateConfirmBtn,
@@ -667,61 +743,53 @@ export const installFromLocalFile = async (
 
       if (!shouldReplace) {
         console.log('用户取消了更新')
-        loadingMap.value.delete('local-file-install')
-        await reader.close()
-        return
-      }
-
-      // 用户确认更新，卸载旧版本
-      console.log('用户确认更新，开始卸载旧版本')
-      try {
-        await emit('uninstall-plugin', { pluginId: pluginId })
-        await deleteFolder(pluginFolderPath)
-        const db = await ClipboardDBService.getInstance()
-        await db.removePlugin(existingPlugin.id)
-      } catch (e) {
-        error('插件卸载失败' + e)
-        message.error(currentLanguage.value.pages.pluginStore.localInstallUninstallFailedHint)
-        loadingMap.value.delete('local-file-install')
-        await reader.close()
         return
       }
     }
 
-    // 关闭当前 reader，重新解压整个文件
-    await reader.close()
-
-    // 创建新的 reader 进行完整解压
-    const newReader = new ZipReader(new BlobReader(blob))
-    loadingMap.value.set('local-file-install', 'unzipping')
+    if (!(await confirmPermissions(manifest.permissions, pluginName, dialog))) {
+      return
+    }
 
-    // 解压文件
-    await extractZipToFolder(newReader, pluginFolderPath)
+    const args = {
+      archivePath,
+      sha256: null,
+      signature,
+      approvedPermissions: manifest.permissions,
+    }
+    if (existingPlugin) {
+      // 升级失败时后端会恢复旧版本
+      await invoke('upgrade_plugin', args)
+    } else {
+      await invoke('install_plugin', args)
+    }
 
-    // 保存插件信息到数据库
+    // 保存插件信息到数据库，平台和描述取自插件的 manifest.json
+    const uiManifest = await loadPluginManifest(pluginId)
     const useLocationSet = await getUseLocations(pluginId)
-    const db = await ClipboardDBService.getInstance()
-    await db.addPlugin({
-      id: 0,
+    const pluginInfo = {
       enable: 1,
       plugin_id: pluginId,
-      plugin_name: manifest.name,
+      plugin_name: uiManifest.name || pluginName,
       version: manifest.version,
       use_location: JSON.stringify([...useLocationSet]),
-      platform: manifest.platform || 'General',
+      platform: uiManifest.platform || 'General',
       url: '', // 本地安装的插件没有 URL
-      description: manifest.description || '',
-      install_time: Date.now(),
-    })
+      description: uiManifest.description || '',
+    }
+    const db = await ClipboardDBService.getInstance()
+    if (existingPlugin) {
+      await db.updatePlugin({ ...pluginInfo, id: existingPlugin.id, install_time: 0 })
+    } else {
+      await db.addPlugin({ ...pluginInfo, id: 0, install_time: Date.now() })
+    }
 
     loadingMap.value.set('local-file-install', 'loading')
 
     // 重新加载插件列表
     await loadLocalPlugins()
 
-    // 后端载入插件的语言
-    await invoke('load_plugin_locales', { pluginId: pluginId })
-    // 前端获取插件的语言
+    // 前端获取插件的语言，后端在安装时已经载入
     await loadPluginLanguage()
 
     // 发送安装事件
@@ -730,7 +798,7 @@ export const installFromLocalFile = async (
     message.success(
       currentLanguage.value.pages.pluginStore.localInstallSuccessHint.replace(
         '${pluginName}',
-        manifest.name
+        pluginInfo.plugin_name
       )
     )
 
@@ -743,50 +811,13 @@ export const installFromLocalFile = async (
     }
   } catch (e) {
     console.error('本地文件安装失败:', e)
-    error('本地文件安装失败' + e)
-    message.error(currentLanguage.value.pages.pluginStore.localInstallFailedHint)
+    error('本地文件安装失败: ' + JSON.stringify(e))
+    showInstallError(e, currentLanguage.value.pages.pluginStore.localInstallFailedHint, message)
   } finally {
     loadingMap.value.delete('local-file-install')
   }
 }
 
-/**
- * 解压 zip 文件到指定文件夹
- * @param reader ZipReader 实例
- * @param pluginFolderPath 目标文件夹路径
- */
-async function extractZipToFolder(
-  reader: ZipReader<BlobReader>,
-  pluginFolderPath: string
-): Promise<void> {
-  await mkdir(pluginFolderPath, { recursive: true })
-  const entries = await reader.getEntries()
-  let processedFiles = 0
-
-  for (const entry of entries) {
-    let relativePath = entry.filename
-    if (!relativePath || relativePath === '/' || relativePath === '\\') {
-      continue
-    }
-
-    if (!entry.directory) {
-      const data = await entry.getData(new BlobWriter())
-      const targetPath = await join(pluginFolderPath, relativePath)
-      const parentDir = await dirname(targetPath)
-      await mkdir(parentDir, { recursive: true })
-      await writeFile(targetPath, new Uint8Array(await data.arrayBuffer()))
-    } else if (relativePath !== '') {
-      const targetPath = await join(pluginFolderPath, relativePath)
-      await mkdir(targetPath, { recursive: true })
-    }
-
-    processedFiles++
-  }
-
-  await reader.close()
-  console.log('文件解压完成，已处理文件数', processedFiles)
-}
-
 /**
  * 初始化插件数据
  */
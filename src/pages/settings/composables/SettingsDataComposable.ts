@@ -21,6 +21,7 @@ export const originalConfig = reactive<Settings>({
   enableTag: true,
   bindTagBtnShowLocation: SETTINGS.TAG.BIND_TAG_LOCATION.TOP_RIGHT,
   autoHideWindow: false,
+  windowPlacement: 'cursor',
   alwaysOnTop: true,
   newVersionAlertMode: SETTINGS.UPDATER.HINT_MODE.TOAST,
   enableAnimationEffects: true,
@@ -31,7 +32,11 @@ export const originalConfig = reactive<Settings>({
   displayThumbnailImage: true,
   imageBasePath: '',
   enableImageSave: true,
+  capturePrimarySelection: false,
+  syncSelections: false,
+  plainTextNormalize: [],
   pluginPath: '',
+  pluginTrustedKeys: [],
   displayDetailTime: false,
   backgroundAnimationEffect: SETTINGS.THEME.BACKGROUND_ANIMATION.DEFAULT_EFFECT,
   searchModel: SETTINGS.SEARCH.DEFAULT,
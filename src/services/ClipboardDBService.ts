@@ -47,7 +47,10 @@ class ClipboardDBService {
                     is_topped   BOOLEAN DEFAULT 0,
                     top_time    INTEGER,
                     type        TEXT    DEFAULT 'text',
-                    file_path   TEXT
+                    file_path   TEXT,
+                    source      TEXT    DEFAULT 'clipboard',
+                    file_meta   TEXT,
+                    file_missing BOOLEAN DEFAULT 0
                 )
             `)
       // 创建标签表
@@ -90,6 +93,18 @@ class ClipboardDBService {
                 )
             `)
 
+      // 创建片段表
+      await this.db?.execute(`
+                CREATE TABLE IF NOT EXISTS snippets
+                (
+                    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name       TEXT    NOT NULL,
+                    content    TEXT    NOT NULL,
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL
+                )
+            `)
+
       const clipboardItemsInfo = await this.db?.select<
         [
           {
@@ -107,6 +122,27 @@ class ClipboardDBService {
                         add link_title TEXT;
                 `)
       }
+      if (!clipboardItemsInfo?.some(col => col.name === 'source')) {
+        // 增加内容来源选区字段
+        await this.db?.execute(`
+                    alter table clipboard_items
+                        add source TEXT DEFAULT 'clipboard';
+                `)
+      }
+      if (!clipboardItemsInfo?.some(col => col.name === 'file_meta')) {
+        // 增加文件元数据字段
+        await this.db?.execute(`
+                    alter table clipboard_items
+                        add file_meta TEXT;
+                `)
+      }
+      if (!clipboardItemsInfo?.some(col => col.name === 'file_missing')) {
+        // 增加文件是否丢失字段
+        await this.db?.execute(`
+                    alter table clipboard_items
+                        add file_missing BOOLEAN DEFAULT 0;
+                `)
+      }
     } catch (er) {
       console.log('[数据库进程] 创建数据库表失败', er)
       error('[数据库进程] 数据库表初始化失败:' + er)
@@ -119,24 +155,28 @@ class ClipboardDBService {
    * @param content 内容
    * @param linkTitle 链接标题
    * @param type 类型
+   * @param source 来源选区，clipboard 或 primary
+   * @param fileMeta 文件元数据（JSON），只有文件类型有
    */
   async saveClipboardItem(
     content: string,
     type: string,
-    linkTitle: string | null = null
+    linkTitle: string | null = null,
+    source: string = 'clipboard',
+    fileMeta: string | null = null
   ): Promise<void> {
     try {
       // 覆盖相同内容的旧记录的复制时间
       if (type === 'text' || type === 'code' || type === 'link') {
         await this.db?.execute(
-          'INSERT INTO clipboard_items (content, copy_time, type, file_path, chars, link_title) VALUES (?, ?, ?, ?, ?, ?)',
-          [content, Date.now(), type, null, content.length, linkTitle]
+          'INSERT INTO clipboard_items (content, copy_time, type, file_path, chars, link_title, source) VALUES (?, ?, ?, ?, ?, ?, ?)',
+          [content, Date.now(), type, null, content.length, linkTitle, source]
         )
       }
       if (type === 'file' || type === 'image') {
         await this.db?.execute(
-          'INSERT INTO clipboard_items (content, copy_time, type, file_path) VALUES (?, ?, ?, ?)',
-          [null, Date.now(), type, content]
+          'INSERT INTO clipboard_items (content, copy_time, type, file_path, file_meta) VALUES (?, ?, ?, ?, ?)',
+          [null, Date.now(), type, content, fileMeta]
         )
       }
       // 清理历史数据
@@ -211,6 +251,34 @@ class ClipboardDBService {
     await this.db?.execute('UPDATE clipboard_items SET copy_time = ? WHERE id = ?', [newTime, id])
   }
 
+  /**
+   * 更新文件元数据
+   * @param id 剪贴板记录id
+   * @param fileMeta 文件元数据（JSON）
+   */
+  async updateItemFileMeta(id: number, fileMeta: string): Promise<void> {
+    await this.db?.execute('UPDATE clipboard_items SET file_meta = ? WHERE id = ?', [fileMeta, id])
+  }
+
+  /**
+   * 更新后台检查得到的文件状态
+   * @param id 剪贴板记录id
+   * @param filePath 文件路径列表（JSON），文件被移动后为新的路径
+   * @param fileMeta 文件元数据（JSON）
+   * @param missing 是否有文件不存在
+   */
+  async updateItemFileState(
+    id: number,
+    filePath: string,
+    fileMeta: string,
+    missing: boolean
+  ): Promise<void> {
+    await this.db?.execute(
+      'UPDATE clipboard_items SET file_path = ?, file_meta = ?, file_missing = ? WHERE id = ?',
+      [filePath, fileMeta, missing ? 1 : 0, id]
+    )
+  }
+
   /**
    * 搜索剪贴板项目 - 游标分页，降低查询性能
    * @param filters 搜索过滤器
@@ -575,6 +643,17 @@ class ClipboardDBService {
     ])) as ClipboardItem[]
   }
 
+  /**
+   * 获取最近复制的条目
+   * @param limit 条数
+   */
+  async getRecentItems(limit: number): Promise<ClipboardItem[]> {
+    return ((await this.db?.select(
+      'SELECT * FROM clipboard_items ORDER BY copy_time DESC LIMIT ?',
+      [limit]
+    )) || []) as ClipboardItem[]
+  }
+
   /**
    * 获取最新条目
    */
@@ -680,6 +759,49 @@ class ClipboardDBService {
     return this.db?.select('SELECT * FROM tags ORDER BY created_at DESC LIMIT 1')
   }
 
+  /**
+   * 添加片段
+   * @param {string} name 片段名称
+   * @param {string} content 片段内容，可包含占位符
+   */
+  async addSnippet(name: string, content: string): Promise<void> {
+    const now = Date.now()
+    await this.db?.execute(
+      'INSERT INTO snippets (name, content, created_at, updated_at) VALUES (?, ?, ?, ?)',
+      [name, content, now, now]
+    )
+  }
+
+  /**
+   * 更新片段
+   * @param {number} id 片段ID
+   * @param {string} name 片段名称
+   * @param {string} content 片段内容
+   */
+  async updateSnippet(id: number, name: string, content: string): Promise<void> {
+    await this.db?.execute(
+      'UPDATE snippets SET name = ?, content = ?, updated_at = ? WHERE id = ?',
+      [name, content, Date.now(), id]
+    )
+  }
+
+  /**
+   * 删除片段
+   * @param {number} id 片段ID
+   */
+  async deleteSnippet(id: number): Promise<void> {
+    await this.db?.execute('DELETE FROM snippets WHERE id = ?', [id])
+  }
+
+  /**
+   * 获取所有片段
+   * @returns {Array} 片段数组，按更新时间降序排列
+   */
+  async getAllSnippets(): Promise<SnippetItem[]> {
+    return ((await this.db?.select('SELECT * FROM snippets ORDER BY updated_at DESC')) ??
+      []) as SnippetItem[]
+  }
+
   /**
    * 删除指定天数之前的剪贴板条目
    * @param days 保留天数
@@ -41,19 +41,28 @@ export async function initClipboardListener(): Promise<UnlistenFn> {
             await db.updateItemTime(id, Date.now())
             info('[数据库进程] 有查询到相同文本内容的记录，覆盖复制时间')
           } else {
+            // 内容来源的选区，PRIMARY 为选中的文本
+            const source = payload.source || 'clipboard'
             if (isUrl(content)) {
               const linkTitle = await getPageTitle(content)
-              await db.saveClipboardItem(content, 'link', linkTitle)
+              await db.saveClipboardItem(content, 'link', linkTitle, source)
             } else if (isCodeText(content)) {
-              await db.saveClipboardItem(content, 'code')
+              await db.saveClipboardItem(content, 'code', null, source)
             } else {
-              await db.saveClipboardItem(content, 'text')
+              await db.saveClipboardItem(content, 'text', null, source)
             }
           }
         }
       } else if (payload.type === 'file') {
         const fileName = payload.file_path
         const files: string[] = JSON.parse(fileName)
+        // 文件元数据（JSON），在后台线程中采集，避免阻塞剪贴板监听
+        const fileMeta: string | null = await getFileMetadata(files)
+          .then(meta => JSON.stringify(meta))
+          .catch(e => {
+            error('采集文件元数据失败: ' + e)
+            return null
+          })
         if (files && files.length === 1 && isImage(files[0])) {
           // 文件用的是双斜杠，图片用的单斜杠，这里需要转义处理
           const id = await db.getFileExist(removeEscape(files[0]), 'image')
@@ -64,11 +73,14 @@ export async function initClipboardListener(): Promise<UnlistenFn> {
             const id = await db.getFileExist(fileName, 'file')
             if (id) {
               await db.updateItemTime(id, Date.now())
+              if (fileMeta) {
+                await db.updateItemFileMeta(id, fileMeta)
+              }
               info('[数据库进程] 有查询到相同文件内容的记录，覆盖复制时间')
             } else {
               if (fileName) {
                 // 直接将文件路径保存到数据库
-                await db.saveClipboardItem(fileName, 'file')
+                await db.saveClipboardItem(fileName, 'file', null, 'clipboard', fileMeta)
               }
             }
           }
@@ -76,11 +88,14 @@ export async function initClipboardListener(): Promise<UnlistenFn> {
           const id = await db.getFileExist(fileName, 'file')
           if (id) {
             await db.updateItemTime(id, Date.now())
+            if (fileMeta) {
+              await db.updateItemFileMeta(id, fileMeta)
+            }
             info('[数据库进程] 有查询到相同文件内容的记录，覆盖复制时间')
           } else {
             if (fileName) {
               // 直接将文件路径保存到数据库
-              await db.saveClipboardItem(fileName, 'file')
+              await db.saveClipboardItem(fileName, 'file', null, 'clipboard', fileMeta)
             }
           }
         }
@@ -104,6 +119,10 @@ export async function initClipboardListener(): Promise<UnlistenFn> {
         const latestItem = await db.getLatestItem()
         if (latestItem) {
           clipboardListen.setItem(latestItem[0])
+          if (latestItem[0].type === 'file') {
+            // 交给后台检查文件是否被移动或删除
+            await trackFileItem(latestItem[0])
+          }
         }
         clipboardListen.success()
         info('剪贴板内容保存完成，更新复制状态')
@@ -120,30 +139,256 @@ export async function initClipboardListener(): Promise<UnlistenFn> {
 }
 
 /**
- * 将内容写入剪贴板
+ * 写入剪贴板前的文本规范化选项
+ */
+export interface NormalizeOptions {
+  // 合并连续的空格和制表符，并去掉行尾空白
+  collapseWhitespace?: boolean
+  // 去掉零宽字符
+  removeZeroWidth?: boolean
+  // 把弯引号替换为直引号
+  replaceSmartQuotes?: boolean
+  // 换行符转换为当前系统的格式
+  platformLineEndings?: boolean
+}
+
+/**
+ * 写入剪贴板的请求
+ */
+export type ClipboardWriteRequest =
+  | { format: 'text'; text: string; normalize?: NormalizeOptions }
+  | { format: 'plainText'; text: string; html?: string; normalize?: NormalizeOptions }
+  | { format: 'html'; html: string; altText?: string }
+  | { format: 'rtf'; rtf: string; altText?: string }
+  | { format: 'image'; path: string; includeFile?: boolean }
+  | { format: 'files'; paths: string[] }
+  | { format: 'multi'; items: ClipboardWriteRequest[] }
+
+/**
+ * 写入剪贴板失败的原因
+ */
+export interface ClipboardWriteError {
+  kind:
+    | 'unavailable'
+    | 'fileNotFound'
+    | 'imageUnreadable'
+    | 'emptyContent'
+    | 'nestedMulti'
+    | 'writeFailed'
+  path?: string
+  message?: string
+}
+
+/**
+ * 后台检查文件记录的结果，只包含状态有变化的记录
+ */
+export interface FileItemValidation {
+  id: number
+  // 更新后的文件路径列表
+  paths: string[]
+  // 不存在并且没有找到新位置的路径
+  missing: string[]
+  relocated: Array<{ from: string; to: string }>
+  // 更新后的文件元数据
+  meta: FileMetadata[]
+}
+
+/**
+ * 设置需要后台检查的所有文件记录
+ * @param items 文件类型的剪贴板记录
+ */
+export async function setTrackedFileItems(items: ClipboardItem[]): Promise<void> {
+  await invoke('set_tracked_file_items', {
+    items: items.map(item => ({ id: item.id, filePath: item.file_path, fileMeta: item.file_meta })),
+  })
+}
+
+/**
+ * 新增需要后台检查的文件记录
+ * @param item 文件类型的剪贴板记录
+ */
+export async function trackFileItem(item: ClipboardItem): Promise<void> {
+  await invoke('track_file_item', {
+    item: { id: item.id, filePath: item.file_path, fileMeta: item.file_meta },
+  })
+}
+
+/**
+ * 文件记录被删除后不再检查
+ * @param id 剪贴板记录id
+ */
+export async function untrackFileItem(id: number): Promise<void> {
+  await invoke('untrack_file_item', { id })
+}
+
+/**
+ * 写入剪贴板，失败时抛出 ClipboardWriteError
+ * @param request 写入请求
+ */
+export async function writeClipboard(request: ClipboardWriteRequest): Promise<void> {
+  await invoke('write_to_clipboard', { request })
+}
+
+/**
+ * 写入剪贴板后切换回打开列表窗口前的窗口并粘贴
+ * @param request 写入请求，为空时直接粘贴当前剪贴板内容
+ * @param caretLeft 粘贴后光标向左移动的字符数
+ */
+export async function pasteToTarget(
+  request?: ClipboardWriteRequest,
+  caretLeft?: number
+): Promise<void> {
+  await invoke('paste_to_target', { request, caretLeft })
+}
+
+/**
+ * 根据剪贴板内容生成写入剪贴板的请求
  * @param item 剪贴板内容对象
  */
-export async function copyToClipboard(item: ClipboardItem): Promise<boolean> {
+export function toWriteRequest(item: ClipboardItem): ClipboardWriteRequest {
   if (item.type === 'text' || item.type === 'code' || item.type === 'link') {
-    // 调用后端接口
-    return await invoke<boolean>('write_to_clipboard', { content: item.content, format: 'text' })
+    return { format: 'text', text: item.content }
   } else if (item.type === 'image') {
-    // 调用后端接口
-    return await invoke<boolean>('write_to_clipboard', { content: item.file_path, format: 'image' })
+    return { format: 'image', path: item.file_path }
   } else {
-    // 调用后端接口
-    return await invoke<boolean>('write_to_clipboard', { content: item.file_path, format: 'files' })
+    return { format: 'files', paths: JSON.parse(item.file_path) }
   }
 }
 
+/**
+ * 将内容写入剪贴板
+ * @param item 剪贴板内容对象
+ */
+export async function copyToClipboard(item: ClipboardItem): Promise<void> {
+  await writeClipboard(toWriteRequest(item))
+}
+
+/**
+ * 以纯文本方式写入剪贴板
+ * @param item 剪贴板内容对象
+ * @param normalize 文本规范化选项
+ */
+export async function copyAsPlainText(
+  item: ClipboardItem,
+  normalize?: NormalizeOptions
+): Promise<void> {
+  await writeClipboard({ format: 'plainText', text: item.content, normalize })
+}
+
 /**
  * 文件写入剪贴板
  * @param filePaths 文件路径列表
  */
-export async function copyFileToClipboard(filePaths: Array<string>): Promise<boolean> {
-  // 调用后端接口
-  return await invoke<boolean>('write_to_clipboard', {
-    content: JSON.stringify(filePaths),
-    format: 'files',
-  })
+export async function copyFileToClipboard(filePaths: Array<string>): Promise<void> {
+  await writeClipboard({ format: 'files', paths: filePaths })
+}
+
+/**
+ * 粘贴队列中的一项
+ */
+export interface PasteQueueItem {
+  // 剪贴板记录id
+  id: string
+  // 显示的摘要
+  label: string
+  request: ClipboardWriteRequest
+}
+
+/**
+ * 加入粘贴队列
+ * @param item 队列项
+ */
+export async function enqueuePaste(item: PasteQueueItem): Promise<void> {
+  await invoke('enqueue_paste', { item })
+}
+
+/**
+ * 查看粘贴队列
+ */
+export async function getPasteQueue(): Promise<PasteQueueItem[]> {
+  return await invoke<PasteQueueItem[]>('get_paste_queue')
+}
+
+/**
+ * 重新排列粘贴队列
+ * @param ids 排序后的id列表
+ */
+export async function reorderPasteQueue(ids: string[]): Promise<void> {
+  await invoke('reorder_paste_queue', { ids })
+}
+
+/**
+ * 从粘贴队列中移除
+ * @param id 队列项id
+ */
+export async function removeFromPasteQueue(id: string): Promise<void> {
+  await invoke('remove_from_paste_queue', { id })
+}
+
+/**
+ * 清空粘贴队列
+ */
+export async function clearPasteQueue(): Promise<void> {
+  await invoke('clear_paste_queue')
+}
+
+/**
+ * 片段渲染结果
+ */
+export interface RenderedSnippet {
+  text: string
+  // {cursor} 所在位置，按字符计算
+  cursor: number | null
+}
+
+/**
+ * 获取片段中需要用户输入的名称，对应 {prompt:名称} 占位符
+ * @param content 片段内容
+ */
+export async function getSnippetPrompts(content: string): Promise<string[]> {
+  return await invoke<string[]>('get_snippet_prompts', { content })
+}
+
+/**
+ * 渲染片段中的占位符
+ * @param content 片段内容
+ * @param values 用户输入的内容，键为 {prompt:名称} 中的名称
+ * @param writeToClipboard 是否将结果写入剪贴板
+ */
+export async function renderSnippet(
+  content: string,
+  values: Record<string, string> = {},
+  writeToClipboard = false
+): Promise<RenderedSnippet> {
+  return await invoke<RenderedSnippet>('render_snippet', { content, values, writeToClipboard })
+}
+
+/**
+ * 暂停监听剪贴板，到时间后自动恢复
+ * @param minutes 暂停时长，单位：分钟，为空时暂停到程序重启
+ */
+export async function pauseListening(minutes?: number): Promise<void> {
+  await invoke('pause_clipboard_listening', { minutes })
+}
+
+/**
+ * 获取文件的元数据
+ * @param paths 文件路径列表
+ */
+export async function getFileMetadata(paths: string[]): Promise<FileMetadata[]> {
+  return await invoke<FileMetadata[]>('get_file_metadata', { paths })
+}
+
+/**
+ * 重新启动剪贴板监听，使 PRIMARY 选区相关设置生效
+ */
+export async function restartClipboardListening(): Promise<void> {
+  await invoke('restart_clipboard_listening')
+}
+
+/**
+ * 获取剪贴板监听运行状况
+ */
+export async function getListenerHealth(): Promise<ListenerHealth> {
+  return await invoke<ListenerHealth>('get_listener_health')
 }
@@ -1,6 +1,7 @@
 import { error, info } from '@tauri-apps/plugin-log'
 import ClipboardDBService from './ClipboardDBService'
 import { getDataRetentionDays, getMaxHistoryItems } from '../store/Settings.ts'
+import { setTrackedFileItems } from './ClipboardService.ts'
 
 /**
  * 数据清理服务
@@ -98,6 +99,8 @@ export default class DataClearService {
       const c1 = await db.clearClipboardItems(this.dataRetentionDays)
       const c2 = await db.clearHistoryItems(this.maxHistoryItems)
       if (c1 + c2 > 0) {
+        // 被清理的文件记录不再需要后台检查
+        await setTrackedFileItems((await db.getItems('file')) || [])
         info(
           `清理历史数据完成，成功删除${c1}条超过保留时长的数据，删除${c2}条超过保留条数限制的数据，保留时长：${this.dataRetentionDays}天，保留条数：${this.maxHistoryItems}条`
         )
@@ -24,6 +24,7 @@ const defaultSettings: Settings = {
   windowWidth: 350,
   windowHeight: 550,
   autoHideWindow: false,
+  windowPlacement: 'cursor',
   alwaysOnTop: true,
   newVersionAlertMode: SETTINGS.UPDATER.HINT_MODE.TOAST,
   enableAnimationEffects: true,
@@ -34,7 +35,11 @@ const defaultSettings: Settings = {
   displayThumbnailImage: true,
   imageBasePath: '',
   enableImageSave: true,
+  capturePrimarySelection: false,
+  syncSelections: false,
+  plainTextNormalize: [],
   pluginPath: '',
+  pluginTrustedKeys: [],
   displayDetailTime: false,
   backgroundAnimationEffect: SETTINGS.THEME.BACKGROUND_ANIMATION.DEFAULT_EFFECT,
   searchModel: SETTINGS.SEARCH.DEFAULT,
@@ -296,6 +301,29 @@ export async function getAutoHideWindow(): Promise<boolean> {
   return value !== undefined && value !== null ? value : defaultSettings.autoHideWindow
 }
 
+/**
+ * 保存窗口打开位置
+ * @param placement 打开位置
+ */
+export async function saveWindowPlacement(placement: Settings['windowPlacement']): Promise<void> {
+  info('保存窗口打开位置: ' + placement)
+  const settings = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  await settings.set(SETTINGS_KEYS.WINDOW_PLACEMENT, placement)
+}
+
+/**
+ * 获取窗口打开位置
+ */
+export async function getWindowPlacement(): Promise<Settings['windowPlacement']> {
+  const store = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  const placement = await store.get<string>(SETTINGS_KEYS.WINDOW_PLACEMENT)
+  // 已经不支持的打开位置（如旧版本的文本光标位置）使用默认值
+  const modes: string[] = ['cursor', 'monitorCenter', 'tray', 'lastPosition']
+  return placement && modes.includes(placement)
+    ? (placement as Settings['windowPlacement'])
+    : defaultSettings.windowPlacement
+}
+
 /**
  * 保存窗口是否始终置顶参数
  * @param alwaysOnTop 窗口是否始终置顶
@@ -491,6 +519,44 @@ export async function getEnableImageSave(): Promise<boolean> {
   return value !== undefined && value !== null ? value : defaultSettings.enableImageSave
 }
 
+/**
+ * 保存是否记录 PRIMARY 选区
+ * @param capturePrimarySelection 是否记录 PRIMARY 选区
+ */
+export async function saveCapturePrimarySelection(capturePrimarySelection: boolean): Promise<void> {
+  info('保存是否记录 PRIMARY 选区: ' + capturePrimarySelection)
+  const settings = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  await settings.set(SETTINGS_KEYS.CAPTURE_PRIMARY_SELECTION, capturePrimarySelection)
+}
+
+/**
+ * 获取是否记录 PRIMARY 选区
+ */
+export async function getCapturePrimarySelection(): Promise<boolean> {
+  const store = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  const value = await store.get<boolean>(SETTINGS_KEYS.CAPTURE_PRIMARY_SELECTION)
+  return value !== undefined && value !== null ? value : defaultSettings.capturePrimarySelection
+}
+
+/**
+ * 保存是否同步 PRIMARY 和 CLIPBOARD 选区
+ * @param syncSelections 是否同步选区
+ */
+export async function saveSyncSelections(syncSelections: boolean): Promise<void> {
+  info('保存是否同步选区: ' + syncSelections)
+  const settings = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  await settings.set(SETTINGS_KEYS.SYNC_SELECTIONS, syncSelections)
+}
+
+/**
+ * 获取是否同步 PRIMARY 和 CLIPBOARD 选区
+ */
+export async function getSyncSelections(): Promise<boolean> {
+  const store = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  const value = await store.get<boolean>(SETTINGS_KEYS.SYNC_SELECTIONS)
+  return value !== undefined && value !== null ? value : defaultSettings.syncSelections
+}
+
 /**
  * 保存插件路径
  * @param pluginPath 插件路径
@@ -509,6 +575,48 @@ export async function getPluginPath(): Promise<string> {
   return (await store.get<string>(SETTINGS_KEYS.PLUGIN_PATH)) || defaultSettings.pluginPath
 }
 
+/**
+ * 保存复制为纯文本时启用的规范化选项
+ * @param plainTextNormalize 启用的选项
+ */
+export async function savePlainTextNormalize(plainTextNormalize: string[]): Promise<void> {
+  info('保存纯文本规范化选项: ' + plainTextNormalize.join(','))
+  const settings = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  await settings.set(SETTINGS_KEYS.PLAIN_TEXT_NORMALIZE, plainTextNormalize)
+}
+
+/**
+ * 获取复制为纯文本时启用的规范化选项
+ */
+export async function getPlainTextNormalize(): Promise<string[]> {
+  const store = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  return (
+    (await store.get<string[]>(SETTINGS_KEYS.PLAIN_TEXT_NORMALIZE)) ||
+    defaultSettings.plainTextNormalize
+  )
+}
+
+/**
+ * 保存可信的插件签名公钥
+ * @param pluginTrustedKeys 签名公钥
+ */
+export async function savePluginTrustedKeys(pluginTrustedKeys: string[]): Promise<void> {
+  info('保存插件签名公钥: ' + pluginTrustedKeys.length)
+  const settings = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  await settings.set(SETTINGS_KEYS.PLUGIN_TRUSTED_KEYS, pluginTrustedKeys)
+}
+
+/**
+ * 获取可信的插件签名公钥
+ */
+export async function getPluginTrustedKeys(): Promise<string[]> {
+  const store = await load(SETTINGS_FILE_NAME, { defaults: {}, autoSave: true })
+  return (
+    (await store.get<string[]>(SETTINGS_KEYS.PLUGIN_TRUSTED_KEYS)) ||
+    defaultSettings.pluginTrustedKeys
+  )
+}
+
 /**
  * 保存是否显示详细时间
  * @param displayDetailTime 是否显示详细时间
@@ -633,6 +741,9 @@ export async function initSettings(): Promise<void> {
     if (!userSettingsString.includes(SETTINGS_KEYS.AUTO_HIDE_WINDOW)) {
       await settings.set(SETTINGS_KEYS.AUTO_HIDE_WINDOW, defaultSettings.autoHideWindow)
     }
+    if (!userSettingsString.includes(SETTINGS_KEYS.WINDOW_PLACEMENT)) {
+      await settings.set(SETTINGS_KEYS.WINDOW_PLACEMENT, defaultSettings.windowPlacement)
+    }
     if (!userSettingsString.includes(SETTINGS_KEYS.ALWAYS_ON_TOP)) {
       await settings.set(SETTINGS_KEYS.ALWAYS_ON_TOP, defaultSettings.alwaysOnTop)
     }
@@ -672,6 +783,15 @@ export async function initSettings(): Promise<void> {
     if (!userSettingsString.includes(SETTINGS_KEYS.ENABLE_IMAGE_SAVE)) {
       await settings.set(SETTINGS_KEYS.ENABLE_IMAGE_SAVE, defaultSettings.enableImageSave)
     }
+    if (!userSettingsString.includes(SETTINGS_KEYS.CAPTURE_PRIMARY_SELECTION)) {
+      await settings.set(
+        SETTINGS_KEYS.CAPTURE_PRIMARY_SELECTION,
+        defaultSettings.capturePrimarySelection
+      )
+    }
+    if (!userSettingsString.includes(SETTINGS_KEYS.SYNC_SELECTIONS)) {
+      await settings.set(SETTINGS_KEYS.SYNC_SELECTIONS, defaultSettings.syncSelections)
+    }
     if (!userSettingsString.includes(SETTINGS_KEYS.PLUGIN_PATH)) {
       await settings.set(
         SETTINGS_KEYS.PLUGIN_PATH,
@@ -715,6 +835,7 @@ export async function initSettings(): Promise<void> {
     await settings.set(SETTINGS_KEYS.WINDOW_WIDTH, defaultSettings.windowWidth)
     await settings.set(SETTINGS_KEYS.WINDOW_HEIGHT, defaultSettings.windowHeight)
     await settings.set(SETTINGS_KEYS.AUTO_HIDE_WINDOW, defaultSettings.autoHideWindow)
+    await settings.set(SETTINGS_KEYS.WINDOW_PLACEMENT, defaultSettings.windowPlacement)
     await settings.set(SETTINGS_KEYS.ALWAYS_ON_TOP, defaultSettings.alwaysOnTop)
     await settings.set(SETTINGS_KEYS.NEW_VERSION_ALERT_MODE, defaultSettings.newVersionAlertMode)
     await settings.set(
@@ -731,6 +852,11 @@ export async function initSettings(): Promise<void> {
       localDataDir + (isMac ? '/' : '\\') + 'images'
     )
     await settings.set(SETTINGS_KEYS.ENABLE_IMAGE_SAVE, defaultSettings.enableImageSave)
+    await settings.set(
+      SETTINGS_KEYS.CAPTURE_PRIMARY_SELECTION,
+      defaultSettings.capturePrimarySelection
+    )
+    await settings.set(SETTINGS_KEYS.SYNC_SELECTIONS, defaultSettings.syncSelections)
     await settings.set(SETTINGS_KEYS.PLUGIN_PATH, localDataDir + (isMac ? '/' : '\\') + 'plugins')
     await settings.set(SETTINGS_KEYS.DISPLAY_DETAIL_TIME, defaultSettings.displayDetailTime)
     await settings.set(
@@ -10,6 +10,23 @@ declare interface ClipboardItem {
   chars: number
   tags_json: string
   link_title: string
+  source: 'clipboard' | 'primary' // 来源选区
+  file_meta: string | null // 文件元数据，FileMetadata[] 的 JSON
+  file_missing: number // 后台检查发现有文件不存在
+}
+
+/**
+ * 复制时采集的文件元数据
+ */
+declare interface FileMetadata {
+  path: string
+  exists: boolean // 采集时文件是否存在
+  isDir: boolean
+  size: number // 文件大小，单位：字节，文件夹为 0
+  modified: number | null // 修改时间的时间戳，单位：毫秒
+  mime: string | null
+  itemCount: number | null // 文件夹中直接包含的数量
+  hash: string | null // 快速哈希，用于查找被移动的文件
 }
 
 declare interface TagItem {
@@ -19,3 +36,11 @@ declare interface TagItem {
   created_at: number
   stats: number
 }
+
+declare interface SnippetItem {
+  id: number
+  name: string
+  content: string
+  created_at: number
+  updated_at: number
+}
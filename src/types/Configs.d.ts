@@ -7,6 +7,7 @@ declare interface Settings {
   windowWidth: number // 窗口宽度
   windowHeight: number // 窗口高度
   autoHideWindow: boolean // 自动隐藏窗口
+  windowPlacement: 'cursor' | 'monitorCenter' | 'tray' | 'lastPosition' // 窗口打开位置
   autoGoToLatestData: boolean // 自动跳转到最新数据
   alwaysOnTop: boolean // 窗口是否始终置顶
   displayThumbnailImage: boolean // 显示缩略图图片
@@ -31,9 +32,13 @@ declare interface Settings {
   maxHistoryItems: number // 最大历史记录数
   dataRetentionDays: number // 数据保留时长
   enableImageSave: boolean // 启用图片保存
+  capturePrimarySelection: boolean // 记录 PRIMARY 选区（Linux）
+  syncSelections: boolean // 同步 PRIMARY 和 CLIPBOARD 选区（Linux）
+  plainTextNormalize: string[] // 复制为纯文本时启用的规范化选项
   imageBasePath: string // 图片保存路径
   // 插件设置
   pluginPath: string // 插件安装路径
+  pluginTrustedKeys: string[] // 可信的插件签名公钥（base64 编码的 ed25519 公钥）
 }
 
 declare interface ShortcutKeys {
@@ -47,3 +52,36 @@ declare interface ShortcutKey {
   name: string
   key: string[]
 }
+
+/**
+ * 后端全局快捷键注册结果
+ */
+declare interface HotkeyStatus {
+  shortcut: string // 快捷键
+  action: 'toggleWindow' | 'toggleListening' | 'pasteRecent' | 'openSearch' | 'pasteQueueNext'
+  index?: number // 快速粘贴的记录序号
+  result: {
+    state: 'registered' | 'disabled' | 'invalid' | 'conflict' | 'failed'
+    message?: string
+    with?: { action: string; index?: number } // 冲突的绑定
+  }
+}
+
+/**
+ * 剪贴板监听状态
+ */
+declare type ListeningStatus =
+  | { status: 'listening' }
+  | { status: 'stopped' }
+  | { status: 'paused'; resumeAt: number | null; remainingSecs: number | null }
+
+/**
+ * 剪贴板监听运行状况
+ */
+declare type ListenerHealth = {
+  running: boolean
+  backend: 'native' | 'wayland' | null // 当前使用的监听实现
+  lastEventAt: number | null // 最近一次检测到剪贴板变化的时间戳，单位：毫秒
+  restartCount: number // 监听异常退出后重启的次数
+  lastError: string | null
+}
@@ -37,10 +37,25 @@ declare interface PageConfig {
     replaceGlobalHotkey: string
     autoHideWindow: string
     alwaysOnTop: string
+    windowPlacement: string
+    placementCursor: string
+    placementMonitorCenter: string
+    placementTray: string
+    placementLastPosition: string
     shortcutKeyOccupationHint: string
     replaceGlobalHotkeyNoPermissionHint: string
     replaceGlobalHotkeyLinkHint: string
     replaceGlobalHotkeyHint: string
+    capturePrimarySelection: string
+    capturePrimarySelectionHint: string
+    syncSelections: string
+    syncSelectionsHint: string
+    plainTextNormalize: string
+    plainTextNormalizeHint: string
+    collapseWhitespace: string
+    removeZeroWidth: string
+    replaceSmartQuotes: string
+    platformLineEndings: string
     languages: string
     autoGoToLatestData: string
     autoGoToLatestDataHint: string
@@ -251,7 +266,11 @@ declare interface PageConfig {
     title: string
     copySuccessMsg: string
     copyFailedMsg: string
+    pasteFailedMsg: string
+    enqueuePasteSuccessMsg: string
+    enqueuePasteFailedMsg: string
     fileNotExistCopyFailedMsg: string
+    fileNotExistPathCopyFailedMsg: string
     searchHint: string
     deleteBtn: string
     bindTagBtn: string
@@ -284,6 +303,9 @@ declare interface PageConfig {
     hoursAgo: string
     daysAgo: string
     linkNotTitle: string
+    fileSize: string
+    fileModified: string
+    folderItemCount: string
     listening: string
     stopListen: string
     alreadyLatestHint: string
@@ -301,6 +323,7 @@ declare interface PageConfig {
       tagManger: string
       dataView: string
       search: string
+      snippets: string
       dataImport: string
       dataExport: string
       clearData: string
@@ -327,6 +350,19 @@ declare interface PageConfig {
       edit: string
       delete: string
       openLink: string
+      enqueuePaste: string
+      copyAsPlainText: string
+      saveAsSnippet: string
+    }
+    snippet: {
+      title: string
+      empty: string
+      untitled: string
+      pasteBtn: string
+      cancelBtn: string
+      saveSuccessMsg: string
+      saveFailedMsg: string
+      pasteFailedMsg: string
     }
   }
   itemEditor: {
@@ -422,6 +458,14 @@ declare interface PageConfig {
     restart: string
     exit: string
     clipboardMonitor: string
+    pasteQueue: string
+    clearPasteQueue: string
+    pauseListening: string
+    pauseFiveMinutes: string
+    pauseOneHour: string
+    pauseUntilRestart: string
+    pausedRemaining: string
+    pausedUntilRestart: string
   }
   preview: {
     title: string
@@ -440,7 +484,11 @@ declare interface PageConfig {
     fileReadFailed: string
     packageDecompressionFailed: string
     cannotPreviewPackage: string
-    cannotPreview7z: string
+    packageEncrypted: string
+    packagePasswordRequired: string
+    packageTooManyEntries: string
+    packageReadTimeout: string
+    packageCorruptPartial: string
     folder: string
   }
   pluginStore: {
@@ -454,6 +502,7 @@ declare interface PageConfig {
     installedBtn: string
     unInstallBtn: string
     settingsBtn: string
+    reinstallBtn: string
     localInstallBtn: string
     pluginPlatformGeneral: string
     pluginPlatformMac: string
@@ -468,7 +517,6 @@ declare interface PageConfig {
     uninstallFailedHint: string
     notInstallHint: string
     updateSuccessHint: string
-    updateUnInstallFailedHint: string
     updateFailedHint: string
     notPluginHint: string
     notSelectPluginHint: string
@@ -479,7 +527,7 @@ declare interface PageConfig {
     updating: string
     localInstallSuccessHint: string
     localInstallFailedHint: string
-    localInstallNoManifestHint: string
+    localInstallNoSignatureHint: string
     localInstallInvalidManifestHint: string
     localInstallUninstallFailedHint: string
     localInstallSameVersionHint: string
@@ -488,6 +536,25 @@ declare interface PageConfig {
     localInstallUpdateContent: string
     localInstallUpdateConfirmBtn: string
     localInstallUpdateCancelBtn: string
+    manifestMissingHint: string
+    noTrustedKeysHint: string
+    invalidSignatureHint: string
+    untrustedSignatureHint: string
+    checksumMismatchHint: string
+    permissionTitle: string
+    permissionContent: string
+    permissionConfirmBtn: string
+    permissionCancelBtn: string
+    permissionHostApiHint: string
+    permissionNotApprovedHint: string
+    approveBtn: string
+    approveSuccessHint: string
+    approveFailedHint: string
+    trustedKeysBtn: string
+    trustedKeysTitle: string
+    trustedKeysHint: string
+    trustedKeysSaveBtn: string
+    trustedKeysSaveSuccessHint: string
     settings: {
       loadError: string
       loadFailed: string
@@ -3,4 +3,21 @@ declare interface PackageInfo {
   dir: boolean
   date: Date
   size: number
+  encrypted?: boolean // 条目是否加密
+}
+
+/**
+ * 读取压缩文件失败的原因
+ */
+declare interface ArchiveError {
+  kind: 'open' | 'corrupt' | 'passwordRequired' | 'partial'
+  message?: string
+  // 只读取了部分条目时，已读取的部分
+  entries?: PackageInfo[]
+  reason?: {
+    kind: 'tooManyEntries' | 'timeout' | 'corrupt'
+    limit?: number
+    seconds?: number
+    message?: string
+  }
 }
@@ -10,6 +10,22 @@ declare interface StorePlugin {
   platform: 'Windows' | 'Mac' | 'General' // 可用平台
   downloadUrl: string // 插件下载地址
   size: number // 插件安装包大小，单位：字节
+  sha256?: string // 插件安装包的 SHA-256 校验和
+  signature?: string // 插件安装包的 ed25519 签名，base64 编码
+}
+
+/**
+ * 插件安装包中的插件清单（plugin.json）
+ */
+declare interface PluginManifest {
+  id: string // 插件唯一id
+  name?: string // 插件名称
+  version: string // 插件版本
+  protocol: number // 插件通信协议版本
+  commands: string[] // 插件提供的命令
+  permissions: string[] // 插件申请的权限
+  locales: string[] // 插件语言文件
+  minAppVersion?: string // 最低应用版本
 }
 
 /**
@@ -41,3 +57,12 @@ declare interface SelectPlugin {
   enable: number // 插件是否启用
   description: string // 插件描述
 }
+
+/**
+ * 插件申请的权限和已授权的权限
+ */
+declare interface PluginPermissions {
+  requested: string[] // 申请的权限
+  approved: string[] // 已授权的权限
+  hostApiOnly: string[] // 只限制宿主 API、不隔离插件进程的权限
+}
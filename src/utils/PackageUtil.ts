@@ -1,9 +1,8 @@
-import { Entry } from '@zip.js/zip.js'
 import { PackageTreeOption } from '../types/PackageTreeOption'
 import { NGradientText, NImage } from 'naive-ui'
 import { convertFileSize } from './FileUtil.ts'
 import { h } from 'vue'
-import { utf8Decoder } from '../constants/PublicConstants.ts'
+import { currentLanguage } from '../services/LanguageService.ts'
 import { packageMimeTypes } from '../constants/FileTypeConstatnts.ts'
 
 /**
@@ -24,29 +23,6 @@ export function getType(filePath: string): string {
   return packageMimeTypes[ext] || ''
 }
 
-/**
- * 读取zip文件
- * @param zipInfo zip文件信息
- * @return 解压后的文件信息
- */
-export async function readZipData(zipInfo: Entry[]): Promise<PackageInfo[]> {
-  const arr: PackageInfo[] = []
-  for (const entry of zipInfo) {
-    // 处理非UTF-8编码的文件名
-    if (!entry.filenameUTF8) {
-      entry.filename = utf8Decoder.decode(entry.rawFilename)
-    }
-    arr.push({
-      path: entry.filename,
-      dir: entry.directory,
-      date: entry.creationDate || new Date(),
-      size: entry.compressedSize,
-    })
-  }
-
-  return arr
-}
-
 /**
  * 填充文件夹树
  * @param packageInfos 包信息
@@ -86,10 +62,14 @@ export function convertChildren(packageInfos: PackageInfo[]): PackageTreeOption[
         suffix: () =>
           h(
             NGradientText,
-            { type: 'success' },
+            // 加密的条目用警告色标出
+            { type: info.encrypted ? 'warning' : 'success' },
             {
               default: () => {
-                return convertFileSize(info.size)
+                const size = convertFileSize(info.size)
+                return info.encrypted
+                  ? `${size} ${currentLanguage.value.pages.preview.packageEncrypted}`
+                  : size
               },
             }
           ),